@@ -0,0 +1,73 @@
+//! Build script generating the ERC-3770 short name table from the curated
+//! chain list subset in `data/chains.csv`.
+
+use std::{collections::BTreeMap, env, fmt::Write as _, fs, path::Path};
+
+const CHAINS: &str = "data/chains.csv";
+
+fn main() {
+    println!("cargo:rerun-if-changed={CHAINS}");
+    if env::var_os("CARGO_FEATURE_ERC3770").is_none() {
+        return;
+    }
+
+    let chains = fs::read_to_string(CHAINS).expect("failed to read chain list");
+    let mut by_short_name = BTreeMap::new();
+    let mut by_chain_id = BTreeMap::new();
+    for (i, line) in chains.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let mut fields = line.splitn(3, ',');
+        let (Some(short_name), Some(chain_id)) = (fields.next(), fields.next()) else {
+            panic!("{CHAINS}:{}: expected `short_name,chain_id,name`", i + 1);
+        };
+        let short_name = short_name.trim().trim_matches('"');
+        let chain_id = chain_id
+            .trim()
+            .parse::<u64>()
+            .unwrap_or_else(|_| panic!("{CHAINS}:{}: invalid chain ID", i + 1));
+        assert!(
+            !short_name.is_empty()
+                && short_name
+                    .bytes()
+                    .all(|b| b == b'-' || b.is_ascii_alphanumeric()),
+            "{CHAINS}:{}: invalid short name `{short_name}`",
+            i + 1,
+        );
+
+        if by_short_name.insert(short_name, chain_id).is_some() {
+            panic!("{CHAINS}:{}: duplicate short name `{short_name}`", i + 1);
+        }
+        if by_chain_id.insert(chain_id, short_name).is_some() {
+            panic!("{CHAINS}:{}: duplicate chain ID {chain_id}", i + 1);
+        }
+    }
+
+    let mut table = String::new();
+    writeln!(
+        table,
+        "static BY_SHORT_NAME: [(&str, u64); {}] = [",
+        by_short_name.len(),
+    )
+    .unwrap();
+    for (short_name, chain_id) in &by_short_name {
+        writeln!(table, "    ({short_name:?}, {chain_id}),").unwrap();
+    }
+    writeln!(table, "];").unwrap();
+    writeln!(
+        table,
+        "static BY_CHAIN_ID: [(u64, &str); {}] = [",
+        by_chain_id.len(),
+    )
+    .unwrap();
+    for (chain_id, short_name) in &by_chain_id {
+        writeln!(table, "    ({chain_id}, {short_name:?}),").unwrap();
+    }
+    writeln!(table, "];").unwrap();
+
+    let out = Path::new(&env::var("OUT_DIR").unwrap()).join("short_names.rs");
+    fs::write(out, table).expect("failed to write short name table");
+}
@@ -0,0 +1 @@
+../../src/keccak.rs
\ No newline at end of file
@@ -5,10 +5,17 @@
 
 extern crate proc_macro;
 
+// The following modules are shared with the `ethaddr` crate. This crate does
+// not have any features, so all feature-gated code in them is disabled and
+// the built-in Keccak-256 implementation is always used.
 #[allow(dead_code)]
 mod buffer;
+#[allow(dead_code, unexpected_cfgs)]
 mod checksum;
+#[allow(dead_code, unexpected_cfgs)]
 mod hex;
+#[allow(dead_code, unexpected_cfgs)]
+mod keccak;
 
 use proc_macro::{Delimiter, Literal, Span, TokenStream, TokenTree};
 use std::fmt::Write as _;
@@ -2,6 +2,7 @@
 //! public addresses.
 
 use core::{
+    fmt::{self, Debug, Display, Formatter},
     mem::{self, MaybeUninit},
     str,
 };
@@ -25,11 +26,29 @@ pub fn fmt(bytes: &[u8; 20], alphabet: Alphabet) -> FormattingBuffer {
         buffer[j + 1].write(nibble(byte & 0xf));
     }
 
-    let buffer = unsafe { mem::transmute(buffer) };
+    let buffer = unsafe { mem::transmute::<[MaybeUninit<u8>; LEN], [u8; LEN]>(buffer) };
     FormattingBuffer(buffer)
 }
 
+/// Format an integer as a decimal string onto a stack-allocated buffer,
+/// returning the buffer and the offset of the first digit. A `u64` has at
+/// most 20 decimal digits.
+pub fn decimal(mut value: u64) -> ([u8; 20], usize) {
+    let mut buffer = [0_u8; 20];
+    let mut start = buffer.len();
+    loop {
+        start -= 1;
+        buffer[start] = b'0' + (value % 10) as u8;
+        value /= 10;
+        if value == 0 {
+            break;
+        }
+    }
+    (buffer, start)
+}
+
 /// A formatting buffer.
+#[derive(Clone, Copy, Eq, Hash, PartialEq)]
 pub struct FormattingBuffer([u8; LEN]);
 
 impl FormattingBuffer {
@@ -58,6 +77,29 @@ impl FormattingBuffer {
     }
 }
 
+/// A formatted address string with a mixed-case checksum.
+#[derive(Clone, Copy, Eq, Hash, PartialEq)]
+pub struct Checksum(pub(crate) FormattingBuffer);
+
+impl Checksum {
+    /// Returns the checksummed address string.
+    pub fn as_str(&self) -> &str {
+        self.0.as_str()
+    }
+}
+
+impl Debug for Checksum {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.debug_tuple("Checksum").field(&self.as_str()).finish()
+    }
+}
+
+impl Display for Checksum {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.pad(self.as_str())
+    }
+}
+
 /// The alphatbet to use.
 pub enum Alphabet {
     Lower,
@@ -0,0 +1,341 @@
+//! CAIP-2 chain IDs and CAIP-10 account IDs.
+//!
+//! CAIP-10 account IDs identify an account across chains by prefixing the
+//! account address with a CAIP-2 chain ID, for example
+//! `eip155:1:0xEeeeeEeeeEeEeeEeEeEeeEEEeeeeEeeeeeeeEEeE` for an account on
+//! Ethereum mainnet.
+
+use crate::{buffer, Address, ParseAddressError, Parser, Prefix};
+use core::{
+    fmt::{self, Debug, Display, Formatter},
+    str::{self, FromStr},
+};
+
+/// The maximum length of a CAIP-2 namespace.
+const NAMESPACE_LEN: usize = 8;
+
+/// The maximum length of a CAIP-2 reference.
+const REFERENCE_LEN: usize = 32;
+
+/// A CAIP-2 chain ID, made up of a namespace and a reference within that
+/// namespace.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```
+/// # use ethaddr::caip::ChainId;
+/// let chain_id = "eip155:100".parse::<ChainId>().unwrap();
+/// assert_eq!(chain_id.namespace(), "eip155");
+/// assert_eq!(chain_id.reference(), "100");
+/// assert_eq!(chain_id, ChainId::eip155(100));
+/// ```
+#[derive(Clone, Copy, Eq, Hash, PartialEq)]
+pub struct ChainId {
+    buffer: [u8; NAMESPACE_LEN + 1 + REFERENCE_LEN],
+    len: u8,
+    split: u8,
+}
+
+impl ChainId {
+    /// Creates a new chain ID from a namespace and reference.
+    pub fn new(namespace: &str, reference: &str) -> Result<Self, ChainIdError> {
+        if !is_namespace(namespace) {
+            return Err(ChainIdError::InvalidNamespace);
+        }
+        if !is_reference(reference) {
+            return Err(ChainIdError::InvalidReference);
+        }
+
+        let mut buffer = [0; NAMESPACE_LEN + 1 + REFERENCE_LEN];
+        let split = namespace.len();
+        let len = split + 1 + reference.len();
+        buffer[..split].copy_from_slice(namespace.as_bytes());
+        buffer[split] = b':';
+        buffer[split + 1..len].copy_from_slice(reference.as_bytes());
+
+        Ok(Self {
+            buffer,
+            len: len as _,
+            split: split as _,
+        })
+    }
+
+    /// Creates a chain ID for an EIP-155 chain.
+    pub fn eip155(chain_id: u64) -> Self {
+        let (digits, start) = buffer::decimal(chain_id);
+        let reference = &digits[start..];
+
+        // A decimal `u64` always fits in the reference.
+        let mut buffer = [0; NAMESPACE_LEN + 1 + REFERENCE_LEN];
+        let len = 7 + reference.len();
+        buffer[..7].copy_from_slice(b"eip155:");
+        buffer[7..len].copy_from_slice(reference);
+
+        Self {
+            buffer,
+            len: len as _,
+            split: 6,
+        }
+    }
+
+    /// Returns the chain ID namespace.
+    pub fn namespace(&self) -> &str {
+        &self.as_str()[..self.split as usize]
+    }
+
+    /// Returns the chain ID reference.
+    pub fn reference(&self) -> &str {
+        &self.as_str()[self.split as usize + 1..]
+    }
+
+    /// Returns the EIP-155 chain ID, or `None` if this chain ID is not in the
+    /// `eip155` namespace.
+    pub fn as_eip155(&self) -> Option<u64> {
+        if self.namespace() != "eip155" {
+            return None;
+        }
+        self.reference().parse().ok()
+    }
+
+    /// Returns the chain ID string.
+    pub fn as_str(&self) -> &str {
+        // SAFETY: Buffer only ever contains ASCII characters.
+        unsafe { str::from_utf8_unchecked(&self.buffer[..self.len as usize]) }
+    }
+}
+
+impl Debug for ChainId {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.debug_tuple("ChainId").field(&self.as_str()).finish()
+    }
+}
+
+impl Display for ChainId {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.pad(self.as_str())
+    }
+}
+
+impl FromStr for ChainId {
+    type Err = ChainIdError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (namespace, reference) = s.split_once(':').ok_or(ChainIdError::InvalidFormat)?;
+        Self::new(namespace, reference)
+    }
+}
+
+/// A CAIP-10 account ID, pairing an address with the chain it belongs to.
+///
+/// Only the CAIP-2 grammar of the chain ID is validated, so addresses on
+/// chains outside the `eip155` namespace that share the Ethereum address
+/// format are also accepted. The address must be `0x`-prefixed.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```
+/// # use ethaddr::{caip::{ChainAccount, ChainId}, Address};
+/// let account = "eip155:1:0xeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeee"
+///     .parse::<ChainAccount>()
+///     .unwrap();
+/// assert_eq!(account.chain_id, ChainId::eip155(1));
+/// assert_eq!(account.address, Address([0xee; 20]));
+/// ```
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct ChainAccount {
+    /// The CAIP-2 chain ID.
+    pub chain_id: ChainId,
+    /// The account address.
+    pub address: Address,
+}
+
+impl ChainAccount {
+    /// Creates a new account ID.
+    pub fn new(chain_id: ChainId, address: Address) -> Self {
+        Self { chain_id, address }
+    }
+
+    /// Creates an account ID for an address on an EIP-155 chain.
+    pub fn eip155(chain_id: u64, address: Address) -> Self {
+        Self::new(ChainId::eip155(chain_id), address)
+    }
+}
+
+impl Display for ChainAccount {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "{}:{}", self.chain_id, self.address)
+    }
+}
+
+impl FromStr for ChainAccount {
+    type Err = ParseChainAccountError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (chain_id, address) = s
+            .rsplit_once(':')
+            .ok_or(ParseChainAccountError::InvalidFormat)?;
+        Ok(Self {
+            chain_id: chain_id.parse().map_err(ParseChainAccountError::ChainId)?,
+            address: Parser::new()
+                .prefix(Prefix::Required)
+                .parse(address)
+                .map_err(ParseChainAccountError::Address)?,
+        })
+    }
+}
+
+/// Returns `true` if the string is a valid CAIP-2 namespace, matching
+/// `[-a-z0-9]{3,8}`.
+fn is_namespace(s: &str) -> bool {
+    (3..=NAMESPACE_LEN).contains(&s.len())
+        && s.bytes()
+            .all(|b| matches!(b, b'-' | b'a'..=b'z' | b'0'..=b'9'))
+}
+
+/// Returns `true` if the string is a valid CAIP-2 reference, matching
+/// `[-_a-zA-Z0-9]{1,32}`.
+fn is_reference(s: &str) -> bool {
+    (1..=REFERENCE_LEN).contains(&s.len())
+        && s.bytes()
+            .all(|b| b == b'-' || b == b'_' || b.is_ascii_alphanumeric())
+}
+
+/// Represents an error parsing a CAIP-2 chain ID.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ChainIdError {
+    /// The chain ID is not of the form `namespace:reference`.
+    InvalidFormat,
+    /// The namespace is invalid.
+    InvalidNamespace,
+    /// The reference is invalid.
+    InvalidReference,
+}
+
+impl Display for ChainIdError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Self::InvalidFormat => write!(f, "chain ID is not of the form `namespace:reference`"),
+            Self::InvalidNamespace => write!(f, "invalid chain ID namespace"),
+            Self::InvalidReference => write!(f, "invalid chain ID reference"),
+        }
+    }
+}
+
+impl core::error::Error for ChainIdError {}
+
+/// Represents an error parsing a CAIP-10 account ID.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ParseChainAccountError {
+    /// The account ID is not of the form `chain_id:address`.
+    InvalidFormat,
+    /// The chain ID is invalid.
+    ChainId(ChainIdError),
+    /// The address is invalid.
+    Address(ParseAddressError),
+}
+
+impl Display for ParseChainAccountError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Self::InvalidFormat => write!(f, "account ID is not of the form `chain_id:address`"),
+            Self::ChainId(err) => write!(f, "invalid account chain ID: {err}"),
+            Self::Address(err) => write!(f, "invalid account address: {err}"),
+        }
+    }
+}
+
+impl core::error::Error for ParseChainAccountError {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        match self {
+            Self::InvalidFormat => None,
+            Self::ChainId(err) => Some(err),
+            Self::Address(err) => Some(err),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn chain_ids() {
+        for (s, namespace, reference) in [
+            ("eip155:1", "eip155", "1"),
+            (
+                "bip122:000000000019d6689c085ae165831e93",
+                "bip122",
+                "000000000019d6689c085ae165831e93",
+            ),
+            ("cosmos:cosmoshub-3", "cosmos", "cosmoshub-3"),
+            ("abc:A_b-C", "abc", "A_b-C"),
+        ] {
+            let chain_id = s.parse::<ChainId>().unwrap();
+            assert_eq!(chain_id.namespace(), namespace);
+            assert_eq!(chain_id.reference(), reference);
+            assert_eq!(chain_id.as_str(), s);
+        }
+
+        assert_eq!(ChainId::eip155(0).as_str(), "eip155:0");
+        assert_eq!(ChainId::eip155(u64::MAX).as_eip155(), Some(u64::MAX));
+        assert_eq!(ChainId::eip155(10).as_eip155(), Some(10));
+        assert_eq!(
+            "cosmos:cosmoshub-3".parse::<ChainId>().unwrap().as_eip155(),
+            None
+        );
+    }
+
+    #[test]
+    fn invalid_chain_ids() {
+        for (s, err) in [
+            ("eip155", ChainIdError::InvalidFormat),
+            ("ab:1", ChainIdError::InvalidNamespace),
+            ("namespace:1", ChainIdError::InvalidNamespace),
+            ("EIP155:1", ChainIdError::InvalidNamespace),
+            ("eip155:", ChainIdError::InvalidReference),
+            ("eip155:1:2", ChainIdError::InvalidReference),
+            (
+                "eip155:0123456789abcdef0123456789abcdef0",
+                ChainIdError::InvalidReference,
+            ),
+        ] {
+            assert_eq!(s.parse::<ChainId>(), Err(err));
+        }
+    }
+
+    #[test]
+    fn chain_accounts() {
+        let account = ChainAccount::eip155(1, Address([0xee; 20]));
+        let s = "eip155:1:0xEeeeeEeeeEeEeeEeEeEeeEEEeeeeEeeeeeeeEEeE";
+        assert_eq!(s.parse::<ChainAccount>().unwrap(), account);
+
+        #[cfg(feature = "checksum")]
+        assert_eq!(account.to_string(), s);
+    }
+
+    #[test]
+    fn invalid_chain_accounts() {
+        assert_eq!(
+            "0xEeeeeEeeeEeEeeEeEeEeeEEEeeeeEeeeeeeeEEeE".parse::<ChainAccount>(),
+            Err(ParseChainAccountError::InvalidFormat),
+        );
+        assert_eq!(
+            "eip155:0xEeeeeEeeeEeEeeEeEeEeeEEEeeeeEeeeeeeeEEeE".parse::<ChainAccount>(),
+            Err(ParseChainAccountError::ChainId(ChainIdError::InvalidFormat)),
+        );
+        assert!(matches!(
+            "eip155:1:0x1234".parse::<ChainAccount>(),
+            Err(ParseChainAccountError::Address(_)),
+        ));
+        assert_eq!(
+            "eip155:1:EeeeeEeeeEeEeeEeEeEeeEEEeeeeEeeeeeeeEEeE".parse::<ChainAccount>(),
+            Err(ParseChainAccountError::Address(
+                ParseAddressError::MissingPrefix
+            )),
+        );
+    }
+}
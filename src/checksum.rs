@@ -2,16 +2,33 @@
 
 use crate::buffer::{self, Alphabet, FormattingBuffer};
 use core::str;
-use sha3::{Digest as _, Keccak256};
 
 /// Format address bytes with EIP-55 checksum.
 pub fn fmt(bytes: &[u8; 20]) -> FormattingBuffer {
+    fmt_with_chain(bytes, None)
+}
+
+/// Format address bytes with an EIP-1191 chain-specific checksum, or EIP-55
+/// checksum when no chain ID is specified.
+pub fn fmt_with_chain(bytes: &[u8; 20], chain_id: Option<u64>) -> FormattingBuffer {
     let mut buffer = buffer::fmt(bytes, Alphabet::Lower);
 
+    // EIP-1191 prefixes the hashed address string with the decimal chain ID,
+    // and unlike EIP-55, includes the `0x` prefix.
+    let decimal = chain_id.map(buffer::decimal);
+    let prefix = match &decimal {
+        Some((decimal, start)) => &decimal[*start..],
+        None => &[],
+    };
+    let digest = if chain_id.is_some() {
+        keccak256_concat(&[prefix, buffer.as_str().as_bytes()])
+    } else {
+        keccak256(buffer.as_bytes_str().as_bytes())
+    };
+
     // SAFETY: We only ever change lowercase ASCII characters to upper case
     // characters, so the buffer remains valid UTF-8 bytes.
     let addr = unsafe { &mut buffer.as_bytes_mut()[2..] };
-    let digest = keccak256(addr);
     for i in 0..addr.len() {
         let byte = digest[i / 2];
         let nibble = 0xf & if i % 2 == 0 { byte >> 4 } else { byte };
@@ -25,16 +42,141 @@ pub fn fmt(bytes: &[u8; 20]) -> FormattingBuffer {
 
 /// Verifies an address checksum.
 pub fn verify(bytes: &[u8; 20], checksum: &str) -> Result<(), FormattingBuffer> {
-    let expected = fmt(bytes);
+    verify_with_chain(bytes, checksum, None)
+}
+
+/// Verifies an address checksum for an optional EIP-1191 chain ID.
+pub fn verify_with_chain(
+    bytes: &[u8; 20],
+    checksum: &str,
+    chain_id: Option<u64>,
+) -> Result<(), FormattingBuffer> {
+    let expected = fmt_with_chain(bytes, chain_id);
     if checksum.strip_prefix("0x").unwrap_or(checksum) != expected.as_bytes_str() {
         return Err(expected);
     }
     Ok(())
 }
 
+/// Determines the checksum status of an address string.
+pub fn status(bytes: &[u8; 20], checksum: &str) -> ChecksumStatus {
+    if verify(bytes, checksum).is_ok() {
+        return ChecksumStatus::Valid;
+    }
+
+    let digits = checksum.strip_prefix("0x").unwrap_or(checksum);
+    if !digits.bytes().any(|b| b.is_ascii_uppercase()) {
+        ChecksumStatus::AllLowercase
+    } else if !digits.bytes().any(|b| b.is_ascii_lowercase()) {
+        ChecksumStatus::AllUppercase
+    } else {
+        ChecksumStatus::Invalid
+    }
+}
+
+/// The EIP-55 checksum status of a parsed address string.
+///
+/// Addresses whose string has a valid checksum are always reported as
+/// [`ChecksumStatus::Valid`], even if they happen to be all lowercase or all
+/// uppercase (for example, addresses without any `a-f` hex digits).
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum ChecksumStatus {
+    /// The address has a valid mixed-case checksum.
+    Valid,
+    /// The address is all lowercase and does not encode a checksum.
+    AllLowercase,
+    /// The address is all uppercase and does not encode a checksum.
+    AllUppercase,
+    /// The address is mixed-case but the checksum does not match.
+    Invalid,
+}
+
+impl ChecksumStatus {
+    /// Returns `true` if the status is acceptable under the usual wallet
+    /// policy, where only mixed-case addresses with invalid checksums are
+    /// rejected.
+    pub fn is_acceptable(&self) -> bool {
+        !matches!(self, Self::Invalid)
+    }
+}
+
 /// Perform Keccak-256 hash over some input bytes.
-fn keccak256(bytes: &[u8]) -> [u8; 32] {
-    let mut hasher = Keccak256::new();
-    hasher.update(bytes);
-    hasher.finalize().into()
+pub fn keccak256(bytes: &[u8]) -> [u8; 32] {
+    keccak256_concat(&[bytes])
+}
+
+/// Perform Keccak-256 hash over the concatenation of multiple input byte
+/// slices.
+pub fn keccak256_concat(parts: &[&[u8]]) -> [u8; 32] {
+    backend::keccak256_concat(parts)
+}
+
+/// Keccak-256 backend using a user-supplied function registered with the
+/// `register_keccak256!` macro.
+#[cfg(all(ethaddr_custom_keccak, feature = "checksum"))]
+mod backend {
+    extern "Rust" {
+        fn __ethaddr_keccak256(parts: &[&[u8]]) -> [u8; 32];
+    }
+
+    pub fn keccak256_concat(parts: &[&[u8]]) -> [u8; 32] {
+        // SAFETY: The function is defined by the `register_keccak256!` macro
+        // with a matching signature.
+        unsafe { __ethaddr_keccak256(parts) }
+    }
+}
+
+/// Keccak-256 backend using the `sha3` crate.
+#[cfg(all(
+    not(all(ethaddr_custom_keccak, feature = "checksum")),
+    feature = "sha3",
+))]
+mod backend {
+    use sha3::{Digest as _, Keccak256};
+
+    pub fn keccak256_concat(parts: &[&[u8]]) -> [u8; 32] {
+        let mut hasher = Keccak256::new();
+        for part in parts {
+            hasher.update(part);
+        }
+        hasher.finalize().into()
+    }
+}
+
+/// Keccak-256 backend using the `tiny-keccak` crate.
+#[cfg(all(
+    not(all(ethaddr_custom_keccak, feature = "checksum")),
+    not(feature = "sha3"),
+    feature = "tiny-keccak",
+))]
+mod backend {
+    use tiny_keccak::{Hasher as _, Keccak};
+
+    pub fn keccak256_concat(parts: &[&[u8]]) -> [u8; 32] {
+        let mut hasher = Keccak::v256();
+        for part in parts {
+            hasher.update(part);
+        }
+        let mut digest = [0; 32];
+        hasher.finalize(&mut digest);
+        digest
+    }
+}
+
+/// Keccak-256 backend using the built-in implementation.
+#[cfg(all(
+    not(all(ethaddr_custom_keccak, feature = "checksum")),
+    not(feature = "sha3"),
+    not(feature = "tiny-keccak"),
+))]
+mod backend {
+    use crate::keccak::Keccak256;
+
+    pub fn keccak256_concat(parts: &[&[u8]]) -> [u8; 32] {
+        let mut hasher = Keccak256::new();
+        for part in parts {
+            hasher.update(part);
+        }
+        hasher.finalize()
+    }
 }
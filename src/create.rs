@@ -0,0 +1,328 @@
+//! Computation of contract addresses created by other accounts.
+
+use crate::{checksum, Address};
+
+/// The Keccak-256 hash of the CREATE3 proxy init code
+/// `0x67363d3d37363d34f03d5260086018f3`.
+const CREATE3_PROXY_INIT_CODE_HASH: [u8; 32] = [
+    0x21, 0xc3, 0x5d, 0xbe, 0x1b, 0x34, 0x4a, 0x24, 0x88, 0xcf, 0x33, 0x21, 0xd6, 0xce, 0x54, 0x2f,
+    0x8e, 0x9f, 0x30, 0x55, 0x44, 0xff, 0x09, 0xe4, 0x99, 0x3a, 0x62, 0x31, 0x9a, 0x49, 0x7c, 0x1f,
+];
+
+impl Address {
+    /// Computes the address of a contract created by this address with the
+    /// `CREATE` opcode (or a contract creation transaction) at the specified
+    /// nonce.
+    ///
+    /// The contract address is the last 20 bytes of the Keccak-256 hash of the
+    /// RLP encoding of `[sender, nonce]`.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// # use ethaddr::Address;
+    /// let deployer = "0x6ac7ea33f8831ea9dcc53393aaa88b25a785dbf0"
+    ///     .parse::<Address>()
+    ///     .unwrap();
+    /// assert_eq!(
+    ///     deployer.create(0).to_string(),
+    ///     "0xcd234A471b72ba2F1Ccf0A70FCABA648a5eeCD8d",
+    /// );
+    /// ```
+    pub fn create(&self, nonce: u64) -> Address {
+        // The RLP encoded `[sender, nonce]` list is at most 31 bytes long:
+        // - 1 byte for the list header
+        // - 21 bytes for the sender address string
+        // - 9 bytes for the nonce integer
+        let mut buffer = [0_u8; 31];
+        buffer[1] = 0x80 + 20;
+        buffer[2..22].copy_from_slice(self.as_slice());
+
+        let nonce_bytes = nonce.to_be_bytes();
+        let nonce_bytes = &nonce_bytes[(nonce.leading_zeros() / 8) as usize..];
+        let nonce_len = match nonce_bytes {
+            // Integers are encoded as big-endian byte strings without leading
+            // zeros, which means that 0 is encoded as the empty string.
+            [] => {
+                buffer[22] = 0x80;
+                1
+            }
+            // Single bytes less than 0x80 are their own encoding.
+            [byte] if *byte < 0x80 => {
+                buffer[22] = *byte;
+                1
+            }
+            _ => {
+                buffer[22] = 0x80 + nonce_bytes.len() as u8;
+                buffer[23..][..nonce_bytes.len()].copy_from_slice(nonce_bytes);
+                1 + nonce_bytes.len()
+            }
+        };
+
+        let len = 22 + nonce_len;
+        buffer[0] = 0xc0 + (len - 1) as u8;
+        let digest = checksum::keccak256(&buffer[..len]);
+        Address::from_slice(&digest[12..])
+    }
+
+    /// Searches for the nonce at which this address created the `target`
+    /// contract with the `CREATE` opcode.
+    ///
+    /// All nonces from `0` up to and including `max_nonce` are searched,
+    /// returning `None` if none of them produce the `target` address.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// # use ethaddr::Address;
+    /// let deployer = "0x6ac7ea33f8831ea9dcc53393aaa88b25a785dbf0"
+    ///     .parse::<Address>()
+    ///     .unwrap();
+    /// let contract = "0xf778b86fa74e846c4f0a1fbd1335fe81c00a0c91"
+    ///     .parse::<Address>()
+    ///     .unwrap();
+    /// assert_eq!(deployer.find_create_nonce(&contract, 10), Some(2));
+    /// assert_eq!(deployer.find_create_nonce(&contract, 1), None);
+    /// ```
+    pub fn find_create_nonce(&self, target: &Address, max_nonce: u64) -> Option<u64> {
+        (0..=max_nonce).find(|nonce| self.create(*nonce) == *target)
+    }
+
+    /// Computes the address of a contract created by this address with the
+    /// `CREATE2` opcode, as specified in EIP-1014.
+    ///
+    /// The contract address is the last 20 bytes of the Keccak-256 hash of
+    /// `0xff ++ deployer ++ salt ++ init_code_hash`.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// # use ethaddr::Address;
+    /// let deployer = Address([0; 20]);
+    /// let init_code_hash = [
+    ///     0xbc, 0x36, 0x78, 0x9e, 0x7a, 0x1e, 0x28, 0x14, 0x36, 0x46, 0x42,
+    ///     0x29, 0x82, 0x8f, 0x81, 0x7d, 0x66, 0x12, 0xf7, 0xb4, 0x77, 0xd6,
+    ///     0x65, 0x91, 0xff, 0x96, 0xa9, 0xe0, 0x64, 0xbc, 0xc9, 0x8a,
+    /// ];
+    /// assert_eq!(
+    ///     deployer.create2([0; 32], init_code_hash).to_string(),
+    ///     "0x4D1A2e2bB4F88F0250f26Ffff098B0b30B26BF38",
+    /// );
+    /// ```
+    pub fn create2(&self, salt: [u8; 32], init_code_hash: [u8; 32]) -> Address {
+        let mut buffer = [0_u8; 85];
+        buffer[0] = 0xff;
+        buffer[1..21].copy_from_slice(self.as_slice());
+        buffer[21..53].copy_from_slice(&salt);
+        buffer[53..85].copy_from_slice(&init_code_hash);
+
+        let digest = checksum::keccak256(&buffer);
+        Address::from_slice(&digest[12..])
+    }
+
+    /// Computes the address of a contract created by this address with the
+    /// `CREATE2` opcode from its raw init code.
+    ///
+    /// This is equivalent to [`Address::create2`] with the Keccak-256 hash of
+    /// the `init_code`.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// # use ethaddr::Address;
+    /// let deployer = Address([0; 20]);
+    /// assert_eq!(
+    ///     deployer.create2_init_code([0; 32], &[0x00]).to_string(),
+    ///     "0x4D1A2e2bB4F88F0250f26Ffff098B0b30B26BF38",
+    /// );
+    /// ```
+    pub fn create2_init_code(&self, salt: [u8; 32], init_code: &[u8]) -> Address {
+        self.create2(salt, checksum::keccak256(init_code))
+    }
+
+    /// Computes the address of a contract created by this factory address with
+    /// the proxy-based CREATE3 pattern.
+    ///
+    /// This is the deployment pattern used by the Solady and 0xSequence
+    /// `CREATE3` libraries, where the factory first deploys a minimal proxy
+    /// with `CREATE2` and the proxy then deploys the contract with `CREATE` at
+    /// nonce 1. As a result, the contract address only depends on the factory
+    /// address and the salt, and not on the contract's init code.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// # use ethaddr::Address;
+    /// let factory = Address([0xee; 20]);
+    /// let salt = [0x42; 32];
+    /// assert_eq!(
+    ///     factory.create3(salt),
+    ///     factory.create3_proxy(salt).create(1),
+    /// );
+    /// ```
+    pub fn create3(&self, salt: [u8; 32]) -> Address {
+        self.create3_proxy(salt).create(1)
+    }
+
+    /// Computes the address of the intermediate proxy contract deployed by
+    /// this factory address with the proxy-based CREATE3 pattern.
+    ///
+    /// See [`Address::create3`] for more details.
+    pub fn create3_proxy(&self, salt: [u8; 32]) -> Address {
+        self.create2(salt, CREATE3_PROXY_INIT_CODE_HASH)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::bytes32;
+
+    #[test]
+    fn create_addresses() {
+        let deployer = "0x6ac7ea33f8831ea9dcc53393aaa88b25a785dbf0"
+            .parse::<Address>()
+            .unwrap();
+        for (nonce, contract) in [
+            (0, "0xcd234a471b72ba2f1ccf0a70fcaba648a5eecd8d"),
+            (1, "0x343c43a37d37dff08ae8c4a11544c718abb4fcf8"),
+            (2, "0xf778b86fa74e846c4f0a1fbd1335fe81c00a0c91"),
+            (3, "0xfffd933a0bc612844eaf0c6fe3e5b8e9b6c1d19c"),
+        ] {
+            assert_eq!(deployer.create(nonce), contract.parse::<Address>().unwrap());
+        }
+    }
+
+    #[test]
+    fn create2_eip1014_vectors() {
+        for (deployer, salt, init_code, contract) in [
+            (
+                "0x0000000000000000000000000000000000000000",
+                "0x0000000000000000000000000000000000000000000000000000000000000000",
+                &[0x00][..],
+                "0x4D1A2e2bB4F88F0250f26Ffff098B0b30B26BF38",
+            ),
+            (
+                "0xdeadbeef00000000000000000000000000000000",
+                "0x0000000000000000000000000000000000000000000000000000000000000000",
+                &[0x00][..],
+                "0xB928f69Bb1D91Cd65274e3c79d8986362984fDA3",
+            ),
+            (
+                "0xdeadbeef00000000000000000000000000000000",
+                "0x000000000000000000000000feed000000000000000000000000000000000000",
+                &[0x00][..],
+                "0xD04116cDd17beBE565EB2422F2497E06cC1C9833",
+            ),
+            (
+                "0x0000000000000000000000000000000000000000",
+                "0x0000000000000000000000000000000000000000000000000000000000000000",
+                &[0xde, 0xad, 0xbe, 0xef][..],
+                "0x70f2b2914A2a4b783FaEFb75f459A580616Fcb5e",
+            ),
+            (
+                "0x00000000000000000000000000000000deadbeef",
+                "0x00000000000000000000000000000000000000000000000000000000cafebabe",
+                &[0xde, 0xad, 0xbe, 0xef][..],
+                "0x60f3f640a8508fC6a86d45DF051962668E1e8AC7",
+            ),
+            (
+                "0x00000000000000000000000000000000deadbeef",
+                "0x00000000000000000000000000000000000000000000000000000000cafebabe",
+                &[0xde, 0xad, 0xbe, 0xef].repeat(11)[..],
+                "0x1d8bfDC5D46DC4f61D6b6115972536eBE6A8854C",
+            ),
+            (
+                "0x0000000000000000000000000000000000000000",
+                "0x0000000000000000000000000000000000000000000000000000000000000000",
+                &[][..],
+                "0xE33C0C7F7df4809055C3ebA6c09CFe4BaF1BD9e0",
+            ),
+        ] {
+            let deployer = deployer.parse::<Address>().unwrap();
+            let salt = bytes32(salt);
+            let contract = Address::from_str_checksum(contract).unwrap();
+
+            assert_eq!(deployer.create2_init_code(salt, init_code), contract);
+            assert_eq!(
+                deployer.create2(salt, checksum::keccak256(init_code)),
+                contract,
+            );
+        }
+    }
+
+    #[test]
+    fn create3_proxy_init_code_hash() {
+        assert_eq!(
+            checksum::keccak256(&[
+                0x67, 0x36, 0x3d, 0x3d, 0x37, 0x36, 0x3d, 0x34, 0xf0, 0x3d, 0x52, 0x60, 0x08, 0x60,
+                0x18, 0xf3,
+            ]),
+            CREATE3_PROXY_INIT_CODE_HASH,
+        );
+    }
+
+    #[test]
+    fn create3_addresses() {
+        for (deployer, salt, proxy, contract) in [
+            (
+                "0x0000000000000000000000000000000000000000",
+                "0x0000000000000000000000000000000000000000000000000000000000000000",
+                "0x205f12c15053b6f69ac2b30ffa1e2d4531c0925c",
+                "0x719bcfed239590F6ff1F33d2F196d01295EDDE1D",
+            ),
+            (
+                "0x00000000000000000000000000000000deadbeef",
+                "0x00000000000000000000000000000000000000000000000000000000cafebabe",
+                "0xda2d337c23cf9dc07446c6c6a8f49fbfba62158a",
+                "0xd24D57281f0dbc7EA64A2DeAF977e65Ee97D4367",
+            ),
+        ] {
+            let deployer = deployer.parse::<Address>().unwrap();
+            let salt = bytes32(salt);
+
+            assert_eq!(
+                deployer.create3_proxy(salt),
+                proxy.parse::<Address>().unwrap()
+            );
+            assert_eq!(
+                deployer.create3(salt),
+                Address::from_str_checksum(contract).unwrap(),
+            );
+        }
+    }
+
+    #[test]
+    fn create_nonce_encoding_boundaries() {
+        // Nonces at the RLP encoding boundaries: the largest single byte
+        // integer, the smallest and largest one byte strings, the smallest
+        // two byte string and the largest nonce.
+        let deployer = "0x6ac7ea33f8831ea9dcc53393aaa88b25a785dbf0"
+            .parse::<Address>()
+            .unwrap();
+        for (nonce, contract) in [
+            (0x7f, "0x06d9a77f5e4b311bae8d559db9cdb4df94104aa0"),
+            (0x80, "0x08e190dcb7b73f5fcdabb43e102215c83659a76d"),
+            (0xff, "0x3ef7c1a519e4b4431e317d7839340e3139b03c65"),
+            (0x100, "0x3837c1ae70354f670550c746580199ac6a73cb0a"),
+            (u64::MAX, "0x9bc924993b60399df164c3763a964301d3db95ca"),
+        ] {
+            let contract = contract.parse::<Address>().unwrap();
+            assert_eq!(deployer.create(nonce), contract);
+            assert_eq!(
+                deployer.find_create_nonce(&contract, nonce.min(0x100)),
+                (nonce <= 0x100).then_some(nonce),
+            );
+        }
+    }
+}
@@ -0,0 +1,293 @@
+//! Address prediction for contracts deployed with the [CreateX] factory.
+//!
+//! CreateX does not use the user-provided salt as is for `CREATE2` and
+//! `CREATE3` deployments. Instead, it computes a "guarded" salt based on the
+//! first 21 bytes of the user salt:
+//! - If the first 20 bytes are the sender address, then the deployment is
+//!   permissioned so that only the sender can deploy to the address.
+//! - If the 21st byte is `0x01`, then the deployment has cross-chain redeploy
+//!   protection, and the address depends on the chain ID.
+//!
+//! See [`guarded_salt`] for the exact computation.
+//!
+//! [CreateX]: https://github.com/pcaversaccio/createx
+
+use crate::{checksum, Address};
+use core::fmt::{self, Display, Formatter};
+
+/// The address of the CreateX factory contract. The factory is deployed to
+/// the same address on all supported chains.
+pub const CREATEX: Address = Address([
+    0xba, 0x5e, 0xd0, 0x99, 0x63, 0x3d, 0x3b, 0x31, 0x3e, 0x4d, 0x5f, 0x7b, 0xdc, 0x13, 0x05, 0xd3,
+    0xc2, 0x8b, 0xa5, 0xed,
+]);
+
+/// Computes the guarded salt that CreateX uses for a `CREATE2` or `CREATE3`
+/// deployment, given the user-provided `salt`, the `sender` of the deployment
+/// transaction and an optional chain ID.
+///
+/// The chain ID is only required for salts with cross-chain redeploy
+/// protection (that is, salts whose 21st byte is `0x01`). This reproduces the
+/// `_guard` function from the CreateX contract exactly, including reverting
+/// (by returning [`SaltError::InvalidSalt`]) on salts that do not explicitly
+/// specify the redeploy protection flag when they are permissioned.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```
+/// # use ethaddr::{createx, Address};
+/// let sender = Address([0xee; 20]);
+/// let mut salt = [0x42; 32];
+///
+/// // Salts with a random prefix are used as is (but hashed).
+/// assert!(createx::guarded_salt(salt, &sender, None).is_ok());
+///
+/// // Salts with redeploy protection require a chain ID.
+/// salt[..20].copy_from_slice(sender.as_slice());
+/// salt[20] = 0x01;
+/// assert!(createx::guarded_salt(salt, &sender, None).is_err());
+/// assert!(createx::guarded_salt(salt, &sender, Some(1)).is_ok());
+/// ```
+pub fn guarded_salt(
+    salt: [u8; 32],
+    sender: &Address,
+    chain_id: Option<u64>,
+) -> Result<[u8; 32], SaltError> {
+    let chain_id = || {
+        let mut word = [0_u8; 32];
+        word[24..].copy_from_slice(&chain_id.ok_or(SaltError::MissingChainId)?.to_be_bytes());
+        Ok(word)
+    };
+
+    let prefix = &salt[..20];
+    let guarded = match (prefix, salt[20]) {
+        (prefix, 0x01) if prefix == sender.as_slice() => {
+            // `keccak256(abi.encode(msg.sender, block.chainid, salt))`
+            let mut buffer = [0_u8; 96];
+            buffer[12..32].copy_from_slice(sender.as_slice());
+            buffer[32..64].copy_from_slice(&chain_id()?);
+            buffer[64..96].copy_from_slice(&salt);
+            checksum::keccak256(&buffer)
+        }
+        (prefix, 0x00) if prefix == sender.as_slice() => {
+            // `keccak256(abi.encode(msg.sender, salt))`
+            let mut buffer = [0_u8; 64];
+            buffer[12..32].copy_from_slice(sender.as_slice());
+            buffer[32..64].copy_from_slice(&salt);
+            checksum::keccak256(&buffer)
+        }
+        (prefix, _) if prefix == sender.as_slice() => return Err(SaltError::InvalidSalt),
+        (prefix, 0x01) if prefix == [0; 20] => {
+            // `keccak256(abi.encode(block.chainid, salt))`
+            let mut buffer = [0_u8; 64];
+            buffer[..32].copy_from_slice(&chain_id()?);
+            buffer[32..64].copy_from_slice(&salt);
+            checksum::keccak256(&buffer)
+        }
+        (prefix, 0x00) if prefix == [0; 20] => checksum::keccak256(&salt),
+        (prefix, _) if prefix == [0; 20] => return Err(SaltError::InvalidSalt),
+        // `keccak256(abi.encode(salt))`
+        _ => checksum::keccak256(&salt),
+    };
+
+    Ok(guarded)
+}
+
+/// Computes the address of a contract deployed by CreateX with `CREATE` at
+/// the specified factory nonce.
+pub fn create(nonce: u64) -> Address {
+    CREATEX.create(nonce)
+}
+
+/// Computes the address of a contract deployed by CreateX with `CREATE2`.
+///
+/// See [`guarded_salt`] for more information on how the `salt`, `sender` and
+/// `chain_id` are used.
+pub fn create2(
+    salt: [u8; 32],
+    sender: &Address,
+    chain_id: Option<u64>,
+    init_code_hash: [u8; 32],
+) -> Result<Address, SaltError> {
+    let salt = guarded_salt(salt, sender, chain_id)?;
+    Ok(CREATEX.create2(salt, init_code_hash))
+}
+
+/// Computes the address of a contract deployed by CreateX with `CREATE3`.
+///
+/// See [`guarded_salt`] for more information on how the `salt`, `sender` and
+/// `chain_id` are used.
+pub fn create3(
+    salt: [u8; 32],
+    sender: &Address,
+    chain_id: Option<u64>,
+) -> Result<Address, SaltError> {
+    let salt = guarded_salt(salt, sender, chain_id)?;
+    Ok(CREATEX.create3(salt))
+}
+
+/// Represents an error computing a CreateX guarded salt.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum SaltError {
+    /// The salt is permissioned but does not explicitly specify whether or
+    /// not it has cross-chain redeploy protection. CreateX reverts with an
+    /// `InvalidSalt` error for these salts.
+    InvalidSalt,
+    /// The salt has cross-chain redeploy protection, but no chain ID was
+    /// specified.
+    MissingChainId,
+}
+
+impl Display for SaltError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Self::InvalidSalt => write!(f, "invalid CreateX salt"),
+            Self::MissingChainId => {
+                write!(f, "chain ID required for salt with redeploy protection")
+            }
+        }
+    }
+}
+
+impl core::error::Error for SaltError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::bytes32;
+
+    fn salt(prefix: &[u8; 20], flag: u8) -> [u8; 32] {
+        let mut salt = [0x42; 32];
+        salt[..20].copy_from_slice(prefix);
+        salt[20] = flag;
+        salt
+    }
+
+    #[test]
+    fn createx_address() {
+        assert_eq!(
+            CREATEX,
+            Address::from_str_checksum("0xba5Ed099633D3B313e4D5F7bdc1305d3c28ba5Ed").unwrap(),
+        );
+    }
+
+    #[test]
+    fn permissioned_salts() {
+        let sender = Address([0xee; 20]);
+
+        let protected = salt(&sender, 0x01);
+        assert_eq!(
+            guarded_salt(protected, &sender, None),
+            Err(SaltError::MissingChainId),
+        );
+        assert_ne!(
+            guarded_salt(protected, &sender, Some(1)).unwrap(),
+            guarded_salt(protected, &sender, Some(100)).unwrap(),
+        );
+
+        let unprotected = salt(&sender, 0x00);
+        assert_eq!(
+            guarded_salt(unprotected, &sender, None),
+            guarded_salt(unprotected, &sender, Some(1)),
+        );
+        assert_ne!(
+            guarded_salt(unprotected, &sender, None).unwrap(),
+            guarded_salt(unprotected, &Address([0; 20]), None).unwrap(),
+        );
+
+        assert_eq!(
+            guarded_salt(salt(&sender, 0x02), &sender, Some(1)),
+            Err(SaltError::InvalidSalt),
+        );
+    }
+
+    #[test]
+    fn zero_address_salts() {
+        let sender = Address([0xee; 20]);
+
+        let protected = salt(&[0; 20], 0x01);
+        assert_eq!(
+            guarded_salt(protected, &sender, None),
+            Err(SaltError::MissingChainId),
+        );
+        assert_eq!(
+            guarded_salt(protected, &sender, Some(1)),
+            guarded_salt(protected, &Address([0xaa; 20]), Some(1)),
+        );
+
+        let unprotected = salt(&[0; 20], 0x00);
+        assert_eq!(
+            guarded_salt(unprotected, &sender, None).unwrap(),
+            checksum::keccak256(&unprotected),
+        );
+
+        assert_eq!(
+            guarded_salt(salt(&[0; 20], 0x02), &sender, Some(1)),
+            Err(SaltError::InvalidSalt),
+        );
+    }
+
+    #[test]
+    fn random_salts() {
+        let sender = Address([0xee; 20]);
+        for flag in [0x00, 0x01, 0x02] {
+            let random = salt(&[0xaa; 20], flag);
+            assert_eq!(
+                guarded_salt(random, &sender, None).unwrap(),
+                checksum::keccak256(&random),
+            );
+        }
+    }
+
+    #[test]
+    fn guarded_salt_vectors() {
+        let sender = "0xd8da6bf26964af9d7eed9e03e53415d37aa96045"
+            .parse::<Address>()
+            .unwrap();
+
+        // `CREATE3` with a permissioned salt and redeploy protection on
+        // mainnet.
+        let salt = bytes32("0xd8da6bf26964af9d7eed9e03e53415d37aa9604501000000000000000000002a");
+        assert_eq!(
+            guarded_salt(salt, &sender, Some(1)).unwrap(),
+            bytes32("0xbcfbd04ffb68b72d249212e09f9b0a7bff7b8786f1015a57efff3e14e9c52f92"),
+        );
+        assert_eq!(
+            create3(salt, &sender, Some(1)).unwrap(),
+            "0xd5a6c59bd937e2b457755f6d16d3570535f34b8d"
+                .parse::<Address>()
+                .unwrap(),
+        );
+
+        // `CREATE2` with a permissioned salt without redeploy protection.
+        let salt = bytes32("0xd8da6bf26964af9d7eed9e03e53415d37aa9604500000000000000000000002a");
+        assert_eq!(
+            guarded_salt(salt, &sender, None).unwrap(),
+            bytes32("0x0aa723c0b50131432e52addbb2ab9aab5b071021aa35df65febb17dad94a98c2"),
+        );
+        assert_eq!(
+            create2(salt, &sender, None, checksum::keccak256(&[0x00])).unwrap(),
+            "0xd0c76351590cf8ba845f9f8cd8f7b7ea8178061b"
+                .parse::<Address>()
+                .unwrap(),
+        );
+    }
+
+    #[test]
+    fn deployment_addresses() {
+        let sender = Address([0xee; 20]);
+        let salt = salt(&sender, 0x00);
+        let guarded = guarded_salt(salt, &sender, None).unwrap();
+
+        assert_eq!(
+            create2(salt, &sender, None, [0x11; 32]).unwrap(),
+            CREATEX.create2(guarded, [0x11; 32]),
+        );
+        assert_eq!(
+            create3(salt, &sender, None).unwrap(),
+            CREATEX.create3(guarded),
+        );
+    }
+}
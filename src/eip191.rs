@@ -0,0 +1,160 @@
+//! EIP-191 signed data hashing and signer recovery.
+//!
+//! This module supports the following EIP-191 versions:
+//! - `0x45` (`E`): personal messages, as signed by the `personal_sign` and
+//!   `eth_sign` JSON RPC methods
+//! - `0x00`: data with an intended validator address
+//!
+//! Version `0x01` (structured data) is specified by EIP-712.
+
+use crate::{buffer, checksum, Address, RecoverError};
+
+/// Computes the EIP-191 personal message hash of a message.
+///
+/// This is the Keccak-256 hash of
+/// `"\x19Ethereum Signed Message:\n" ++ len(message) ++ message`, where the
+/// message length is encoded as a decimal string.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```
+/// # use ethaddr::eip191;
+/// assert_eq!(
+///     eip191::personal_message_hash(b"Hello World"),
+///     [
+///         0xa1, 0xde, 0x98, 0x86, 0x00, 0xa4, 0x2c, 0x4b, 0x4a, 0xb0, 0x89,
+///         0xb6, 0x19, 0x29, 0x7c, 0x17, 0xd5, 0x3c, 0xff, 0xae, 0x5d, 0x51,
+///         0x20, 0xd8, 0x2d, 0x8a, 0x92, 0xd0, 0xbb, 0x3b, 0x78, 0xf2,
+///     ],
+/// );
+/// ```
+pub fn personal_message_hash(message: &[u8]) -> [u8; 32] {
+    let (len, start) = buffer::decimal(message.len() as u64);
+    checksum::keccak256_concat(&[b"\x19Ethereum Signed Message:\n", &len[start..], message])
+}
+
+/// Recovers the signer address of an EIP-191 personal message signature.
+///
+/// See [`Address::recover`] for the supported signature encodings.
+pub fn recover_personal_message(message: &[u8], signature: &[u8]) -> Result<Address, RecoverError> {
+    Address::recover(personal_message_hash(message), signature)
+}
+
+/// Verifies that an EIP-191 personal message signature was signed by the
+/// `expected` address, as produced by `personal_sign`.
+///
+/// Returns `false` if the signature is invalid or was signed by a different
+/// address.
+pub fn verify_personal_sign(expected: &Address, message: &[u8], signature: &[u8]) -> bool {
+    recover_personal_message(message, signature).as_ref() == Ok(expected)
+}
+
+/// Computes the EIP-191 version `0x00` hash of data with an intended
+/// validator.
+///
+/// This is the Keccak-256 hash of `0x19 ++ 0x00 ++ validator ++ data`.
+pub fn validator_message_hash(validator: &Address, data: &[u8]) -> [u8; 32] {
+    checksum::keccak256_concat(&[&[0x19, 0x00], validator.as_slice(), data])
+}
+
+/// Recovers the signer address of an EIP-191 version `0x00` signature of data
+/// with an intended validator.
+///
+/// See [`Address::recover`] for the supported signature encodings.
+pub fn recover_validator_message(
+    validator: &Address,
+    data: &[u8],
+    signature: &[u8],
+) -> Result<Address, RecoverError> {
+    Address::recover(validator_message_hash(validator, data), signature)
+}
+
+/// Verifies that an EIP-191 version `0x00` signature of data with an intended
+/// validator was signed by the `expected` address.
+///
+/// Returns `false` if the signature is invalid or was signed by a different
+/// address.
+pub fn verify_validator_message(
+    expected: &Address,
+    validator: &Address,
+    data: &[u8],
+    signature: &[u8],
+) -> bool {
+    recover_validator_message(validator, data, signature).as_ref() == Ok(expected)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use k256::ecdsa::SigningKey;
+
+    fn sign(key: &SigningKey, prehash: &[u8; 32]) -> Vec<u8> {
+        let (signature, recovery_id) = key.sign_prehash_recoverable(prehash).unwrap();
+        [&signature.to_bytes()[..], &[recovery_id.to_byte() + 27]].concat()
+    }
+
+    fn signer(key: &SigningKey) -> Address {
+        Address::from_public_key(key.verifying_key().to_encoded_point(false).as_bytes()).unwrap()
+    }
+
+    #[test]
+    fn personal_message_lengths() {
+        for (message, prefixed) in [
+            (&b""[..], &b"\x19Ethereum Signed Message:\n0"[..]),
+            (b"a", b"\x19Ethereum Signed Message:\n1a"),
+            (b"0123456789", b"\x19Ethereum Signed Message:\n100123456789"),
+        ] {
+            assert_eq!(
+                personal_message_hash(message),
+                checksum::keccak256(prefixed),
+            );
+        }
+    }
+
+    #[test]
+    fn verify_personal_messages() {
+        let key = SigningKey::from_slice(&[0x01; 32]).unwrap();
+        let message = b"Sign in to ethaddr";
+        let signature = sign(&key, &personal_message_hash(message));
+
+        assert_eq!(
+            recover_personal_message(message, &signature),
+            Ok(signer(&key)),
+        );
+        assert!(verify_personal_sign(&signer(&key), message, &signature));
+        assert!(!verify_personal_sign(
+            &Address([0; 20]),
+            message,
+            &signature
+        ));
+        assert!(!verify_personal_sign(&signer(&key), b"other", &signature));
+        assert!(!verify_personal_sign(&signer(&key), message, &[0; 65]));
+    }
+
+    #[test]
+    fn verify_validator_messages() {
+        let key = SigningKey::from_slice(&[0x01; 32]).unwrap();
+        let validator = Address([0xee; 20]);
+        let data = &[0x01, 0x02, 0x03];
+        let signature = sign(&key, &validator_message_hash(&validator, data));
+
+        assert_eq!(
+            validator_message_hash(&validator, data),
+            checksum::keccak256(&[&[0x19, 0x00], &validator[..], data].concat()),
+        );
+        assert!(verify_validator_message(
+            &signer(&key),
+            &validator,
+            data,
+            &signature,
+        ));
+        assert!(!verify_validator_message(
+            &signer(&key),
+            &Address([0; 20]),
+            data,
+            &signature,
+        ));
+    }
+}
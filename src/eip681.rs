@@ -0,0 +1,516 @@
+//! EIP-681 transaction request URIs.
+//!
+//! EIP-681 URIs describe a transaction to be sent, and are commonly used for
+//! payment requests and deposits, for example
+//! `ethereum:0xToken@1/transfer?address=0xRecipient&uint256=1e6` for an ERC-20
+//! token transfer. Only address targets are supported, ENS names are not.
+
+use crate::{Address, ParseAddressError};
+use core::{
+    fmt::{self, Display, Formatter, Write as _},
+    str::FromStr,
+};
+
+/// The URI scheme of transaction requests.
+const SCHEME: &str = "ethereum:";
+
+/// An EIP-681 transaction request URI.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```
+/// # use ethaddr::{eip681::{Uri, Value}, Address};
+/// let uri = "ethereum:0xEeeeeEeeeEeEeeEeEeEeeEEEeeeeEeeeeeeeEEeE@1/transfer\
+///            ?address=0x90F8bf6A479f320ead074411a4B0e7944Ea8c9C1&uint256=1e6"
+///     .parse::<Uri>()
+///     .unwrap();
+/// assert_eq!(uri.target, Address([0xee; 20]));
+/// assert_eq!(uri.chain_id, Some(1));
+/// assert_eq!(uri.function.as_deref(), Some("transfer"));
+/// assert_eq!(uri.parameters[0].key, "address");
+/// assert!(matches!(uri.parameters[0].value, Value::Address(_)));
+/// match &uri.parameters[1].value {
+///     Value::Number(amount) => assert_eq!(amount.to_u128(), Some(1_000_000)),
+///     _ => unreachable!(),
+/// }
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Uri {
+    /// Whether the URI has the `pay-` prefix.
+    pub pay: bool,
+    /// The target address of the transaction.
+    pub target: Address,
+    /// The EIP-155 chain ID of the transaction.
+    pub chain_id: Option<u64>,
+    /// The name of the contract function to call.
+    pub function: Option<String>,
+    /// The transaction or function call parameters.
+    pub parameters: Vec<Parameter>,
+}
+
+impl Uri {
+    /// Creates a new URI for a transaction to the specified target address.
+    pub fn new(target: Address) -> Self {
+        Self {
+            pay: false,
+            target,
+            chain_id: None,
+            function: None,
+            parameters: Vec::new(),
+        }
+    }
+}
+
+#[cfg(feature = "qr")]
+impl Uri {
+    /// Encodes the URI as a QR code.
+    pub fn to_qr_code(
+        &self,
+        ec: crate::qr::EcLevel,
+    ) -> Result<crate::qr::QrCode, crate::qr::QrError> {
+        crate::qr::QrCode::encode(self.to_string().as_bytes(), ec)
+    }
+}
+
+impl Display for Uri {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.write_str(SCHEME)?;
+        if self.pay {
+            f.write_str("pay-")?;
+        }
+        write!(f, "{}", self.target)?;
+        if let Some(chain_id) = self.chain_id {
+            write!(f, "@{chain_id}")?;
+        }
+        if let Some(function) = &self.function {
+            write!(f, "/{}", PercentEncoded(function))?;
+        }
+        for (i, parameter) in self.parameters.iter().enumerate() {
+            let separator = if i == 0 { '?' } else { '&' };
+            write!(f, "{separator}{}=", PercentEncoded(&parameter.key))?;
+            match &parameter.value {
+                Value::Number(number) => write!(f, "{number}")?,
+                Value::Address(address) => write!(f, "{address}")?,
+                Value::String(string) => write!(f, "{}", PercentEncoded(string))?,
+            }
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for Uri {
+    type Err = ParseUriError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.strip_prefix(SCHEME).ok_or(ParseUriError::InvalidScheme)?;
+        let (pay, s) = match s.strip_prefix("pay-") {
+            Some(s) => (true, s),
+            None => (false, s),
+        };
+        let (s, query) = match s.split_once('?') {
+            Some((s, query)) => (s, Some(query)),
+            None => (s, None),
+        };
+        let (s, function) = match s.split_once('/') {
+            Some((s, function)) => (s, Some(function)),
+            None => (s, None),
+        };
+        let (target, chain_id) = match s.split_once('@') {
+            Some((target, chain_id)) => (target, Some(chain_id)),
+            None => (s, None),
+        };
+
+        let target = parse_address(target)?;
+        let chain_id = chain_id
+            .map(|chain_id| {
+                if chain_id.is_empty() || !chain_id.bytes().all(|b| b.is_ascii_digit()) {
+                    return Err(ParseUriError::InvalidChainId);
+                }
+                chain_id.parse().map_err(|_| ParseUriError::InvalidChainId)
+            })
+            .transpose()?;
+        let function = function
+            .map(|function| match percent_decode(function)? {
+                function if function.is_empty() => Err(ParseUriError::InvalidFunctionName),
+                function => Ok(function),
+            })
+            .transpose()?;
+
+        let mut parameters = Vec::new();
+        for parameter in query.into_iter().flat_map(|query| query.split('&')) {
+            let (key, value) = parameter
+                .split_once('=')
+                .ok_or(ParseUriError::InvalidParameter)?;
+            let key = percent_decode(key)?;
+            if key.is_empty() {
+                return Err(ParseUriError::InvalidParameter);
+            }
+            let value = match key.as_str() {
+                "address" => Value::Address(parse_address(value)?),
+                key if is_numeric(key) => Value::Number(value.parse()?),
+                _ => Value::String(percent_decode(value)?),
+            };
+            parameters.push(Parameter { key, value });
+        }
+
+        Ok(Self {
+            pay,
+            target,
+            chain_id,
+            function,
+            parameters,
+        })
+    }
+}
+
+/// A transaction or function call parameter.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Parameter {
+    /// The parameter key, either a Solidity type for function call arguments
+    /// or one of `value`, `gas`, `gasLimit` or `gasPrice`.
+    pub key: String,
+    /// The parameter value.
+    pub value: Value,
+}
+
+impl Parameter {
+    /// Creates a new parameter.
+    pub fn new(key: impl Into<String>, value: Value) -> Self {
+        Self {
+            key: key.into(),
+            value,
+        }
+    }
+}
+
+/// A parameter value.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Value {
+    /// A number, used for `value`, gas and integer parameters.
+    Number(Number),
+    /// An address, used for `address` parameters.
+    Address(Address),
+    /// A string, used for all other parameters.
+    String(String),
+}
+
+/// A number parameter value, which may be in scientific notation.
+///
+/// The number keeps its original string representation, so that URIs are
+/// formatted exactly as they were parsed.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Number(String);
+
+impl Number {
+    /// Returns the number string.
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// Returns the value of the number as an unsigned integer. Returns `None`
+    /// if the number is negative, not an integer, or does not fit in a
+    /// `u128`.
+    pub fn to_u128(&self) -> Option<u128> {
+        let s = self.0.strip_prefix('+').unwrap_or(&self.0);
+        let (mantissa, exponent) = match s.split_once(['e', 'E']) {
+            Some((mantissa, "")) => (mantissa, 0),
+            Some((mantissa, exponent)) => (mantissa, exponent.parse::<u32>().ok()?),
+            None => (s, 0),
+        };
+        let (integer, fraction) = mantissa.split_once('.').unwrap_or((mantissa, ""));
+        let (negative, integer) = match integer.strip_prefix('-') {
+            Some(integer) => (true, integer),
+            None => (false, integer),
+        };
+
+        // Trailing fractional zeros do not affect the value, any others need
+        // to be shifted into the integer part by the exponent.
+        let fraction = fraction.trim_end_matches('0');
+        let shift = exponent.checked_sub(fraction.len() as u32)?;
+
+        let mut value = 0_u128;
+        for digit in integer.bytes().chain(fraction.bytes()) {
+            value = value.checked_mul(10)?.checked_add((digit - b'0') as u128)?;
+        }
+        value = value.checked_mul(10_u128.checked_pow(shift)?)?;
+
+        if negative && value != 0 {
+            return None;
+        }
+        Some(value)
+    }
+}
+
+impl Display for Number {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl From<u128> for Number {
+    fn from(value: u128) -> Self {
+        Self(value.to_string())
+    }
+}
+
+impl FromStr for Number {
+    type Err = ParseUriError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        // number = [ "-" / "+" ] *DIGIT [ "." 1*DIGIT ] [ ( "e" / "E" ) [ 1*DIGIT ] ]
+        let digits = |s: &str| s.bytes().all(|b| b.is_ascii_digit());
+        let unsigned = s.strip_prefix(['-', '+']).unwrap_or(s);
+        let (mantissa, exponent) = unsigned.split_once(['e', 'E']).unwrap_or((unsigned, ""));
+        let (integer, fraction) = match mantissa.split_once('.') {
+            Some((integer, fraction)) if !fraction.is_empty() => (integer, fraction),
+            Some(_) => return Err(ParseUriError::InvalidNumber),
+            None => (mantissa, ""),
+        };
+        if mantissa.is_empty() || !digits(integer) || !digits(fraction) || !digits(exponent) {
+            return Err(ParseUriError::InvalidNumber);
+        }
+        Ok(Self(s.to_owned()))
+    }
+}
+
+/// Parses an address in a URI, requiring the `0x` prefix and a valid EIP-55
+/// checksum.
+fn parse_address(s: &str) -> Result<Address, ParseUriError> {
+    if !s.starts_with("0x") {
+        return Err(ParseUriError::InvalidAddress(
+            ParseAddressError::MissingPrefix,
+        ));
+    }
+    Address::from_str_checksum(s).map_err(ParseUriError::InvalidAddress)
+}
+
+/// Returns `true` if the parameter key has a numeric value.
+fn is_numeric(key: &str) -> bool {
+    matches!(key, "value" | "gas" | "gasLimit" | "gasPrice")
+        || ["uint", "int"].into_iter().any(|prefix| {
+            key.strip_prefix(prefix)
+                .is_some_and(|bits| bits.bytes().all(|b| b.is_ascii_digit()))
+        })
+}
+
+/// Decodes a percent-encoded URI component.
+fn percent_decode(s: &str) -> Result<String, ParseUriError> {
+    let mut bytes = Vec::with_capacity(s.len());
+    let mut iter = s.bytes();
+    while let Some(b) = iter.next() {
+        if b != b'%' {
+            bytes.push(b);
+            continue;
+        }
+        let hex = [iter.next(), iter.next()];
+        let [Some(hi), Some(lo)] = hex.map(|b| b.and_then(|b| (b as char).to_digit(16))) else {
+            return Err(ParseUriError::InvalidPercentEncoding);
+        };
+        bytes.push((hi << 4 | lo) as u8);
+    }
+    String::from_utf8(bytes).map_err(|_| ParseUriError::InvalidPercentEncoding)
+}
+
+/// Formats a URI component, percent-encoding all characters other than RFC
+/// 3986 unreserved characters.
+struct PercentEncoded<'a>(&'a str);
+
+impl Display for PercentEncoded<'_> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        for b in self.0.bytes() {
+            if b.is_ascii_alphanumeric() || matches!(b, b'-' | b'.' | b'_' | b'~') {
+                f.write_char(b as char)?;
+            } else {
+                write!(f, "%{b:02X}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Represents an error parsing an EIP-681 URI.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ParseUriError {
+    /// The URI does not have the `ethereum:` scheme.
+    InvalidScheme,
+    /// An address in the URI is invalid or does not have a valid EIP-55
+    /// checksum.
+    InvalidAddress(ParseAddressError),
+    /// The chain ID is invalid.
+    InvalidChainId,
+    /// The function name is invalid.
+    InvalidFunctionName,
+    /// A parameter is not of the form `key=value`.
+    InvalidParameter,
+    /// A number parameter value is invalid.
+    InvalidNumber,
+    /// The URI contains invalid percent-encoded characters.
+    InvalidPercentEncoding,
+}
+
+impl Display for ParseUriError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Self::InvalidScheme => write!(f, "URI does not have the `ethereum:` scheme"),
+            Self::InvalidAddress(err) => write!(f, "invalid URI address: {err}"),
+            Self::InvalidChainId => write!(f, "invalid URI chain ID"),
+            Self::InvalidFunctionName => write!(f, "invalid URI function name"),
+            Self::InvalidParameter => write!(f, "invalid URI parameter"),
+            Self::InvalidNumber => write!(f, "invalid URI number"),
+            Self::InvalidPercentEncoding => write!(f, "invalid URI percent-encoding"),
+        }
+    }
+}
+
+impl core::error::Error for ParseUriError {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        match self {
+            Self::InvalidAddress(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TOKEN: &str = "0xEeeeeEeeeEeEeeEeEeEeeEEEeeeeEeeeeeeeEEeE";
+    const RECIPIENT: &str = "0x90F8bf6A479f320ead074411a4B0e7944Ea8c9C1";
+
+    #[test]
+    fn roundtrip() {
+        for s in [
+            format!("ethereum:{TOKEN}"),
+            format!("ethereum:pay-{TOKEN}@137"),
+            format!("ethereum:{RECIPIENT}?value=2.014e18&gasLimit=21000"),
+            format!("ethereum:{TOKEN}@1/transfer?address={RECIPIENT}&uint256=1e6"),
+            format!("ethereum:{TOKEN}/setName?string=hello%20world%21&bytes32=0xabcd"),
+        ] {
+            assert_eq!(s.parse::<Uri>().unwrap().to_string(), s);
+        }
+    }
+
+    #[test]
+    fn parse_uri() {
+        let uri = format!("ethereum:pay-{TOKEN}@10/approve?address={RECIPIENT}&uint256=-0")
+            .parse::<Uri>()
+            .unwrap();
+        assert_eq!(
+            uri,
+            Uri {
+                pay: true,
+                target: Address([0xee; 20]),
+                chain_id: Some(10),
+                function: Some("approve".to_owned()),
+                parameters: vec![
+                    Parameter::new("address", Value::Address(RECIPIENT.parse().unwrap())),
+                    Parameter::new("uint256", Value::Number("-0".parse().unwrap())),
+                ],
+            },
+        );
+
+        let uri = format!("ethereum:{TOKEN}/f%C3%BCr?string=%E2%9C%93")
+            .parse::<Uri>()
+            .unwrap();
+        assert_eq!(uri.function.as_deref(), Some("für"));
+        assert_eq!(uri.parameters[0].value, Value::String("✓".to_owned()));
+    }
+
+    #[test]
+    fn format_uri() {
+        let mut uri = Uri::new(Address([0xee; 20]));
+        uri.chain_id = Some(1);
+        uri.function = Some("transfer".to_owned());
+        uri.parameters = vec![
+            Parameter::new("address", Value::Address(RECIPIENT.parse().unwrap())),
+            Parameter::new("uint256", Value::Number(1_000_000.into())),
+            Parameter::new("string", Value::String("a&b=c".to_owned())),
+        ];
+        assert_eq!(
+            uri.to_string(),
+            format!(
+                "ethereum:{TOKEN}@1/transfer?address={RECIPIENT}&uint256=1000000&string=a%26b%3Dc"
+            ),
+        );
+    }
+
+    #[test]
+    fn number_values() {
+        for (s, value) in [
+            ("0", Some(0)),
+            ("1e6", Some(1_000_000)),
+            ("1E6", Some(1_000_000)),
+            ("+42", Some(42)),
+            ("2.014e18", Some(2_014_000_000_000_000_000)),
+            ("1.50e1", Some(15)),
+            ("1e", Some(1)),
+            ("-0", Some(0)),
+            ("-1", None),
+            ("1.5", None),
+            ("1e39", None),
+        ] {
+            assert_eq!(s.parse::<Number>().unwrap().to_u128(), value);
+        }
+
+        for s in ["", "-", "1.", ".", "1e-6", "0x1", "1.2.3"] {
+            assert_eq!(s.parse::<Number>(), Err(ParseUriError::InvalidNumber));
+        }
+    }
+
+    #[test]
+    fn invalid_uris() {
+        for (s, err) in [
+            (format!("bitcoin:{TOKEN}"), ParseUriError::InvalidScheme),
+            (
+                "ethereum:vitalik.eth".to_owned(),
+                ParseUriError::InvalidAddress(ParseAddressError::MissingPrefix),
+            ),
+            (format!("ethereum:{TOKEN}@"), ParseUriError::InvalidChainId),
+            (
+                format!("ethereum:{TOKEN}@0x1"),
+                ParseUriError::InvalidChainId,
+            ),
+            (
+                format!("ethereum:{TOKEN}/"),
+                ParseUriError::InvalidFunctionName,
+            ),
+            (
+                format!("ethereum:{TOKEN}?value"),
+                ParseUriError::InvalidParameter,
+            ),
+            (
+                format!("ethereum:{TOKEN}?=1"),
+                ParseUriError::InvalidParameter,
+            ),
+            (
+                format!("ethereum:{TOKEN}?value=abc"),
+                ParseUriError::InvalidNumber,
+            ),
+            (
+                format!("ethereum:{TOKEN}?string=%ZZ"),
+                ParseUriError::InvalidPercentEncoding,
+            ),
+            (
+                format!("ethereum:{TOKEN}?string=%FF"),
+                ParseUriError::InvalidPercentEncoding,
+            ),
+        ] {
+            assert_eq!(s.parse::<Uri>(), Err(err));
+        }
+
+        // Addresses require valid checksums, just like the target address.
+        assert!(matches!(
+            format!(
+                "ethereum:{TOKEN}/transfer?address={}",
+                RECIPIENT.to_lowercase()
+            )
+            .parse::<Uri>(),
+            Err(ParseUriError::InvalidAddress(
+                ParseAddressError::ChecksumMismatch { .. }
+            )),
+        ));
+    }
+}
@@ -0,0 +1,806 @@
+//! EIP-712 typed structured data hashing and signer recovery.
+//!
+//! Typed data is specified in the JSON format used by the
+//! `eth_signTypedData_v4` JSON RPC method:
+//!
+//! ```json
+//! {
+//!   "types": {
+//!     "EIP712Domain": [{ "name": "name", "type": "string" }, ...],
+//!     "Mail": [{ "name": "contents", "type": "string" }, ...]
+//!   },
+//!   "primaryType": "Mail",
+//!   "domain": { "name": "Ether Mail", ... },
+//!   "message": { "contents": "Hello, Bob!", ... }
+//! }
+//! ```
+//!
+//! Typed data is fully validated when it is parsed, so that invalid types or
+//! values (such as invalid addresses) are rejected early.
+
+use crate::{checksum, Address, ParseAddressError, RecoverError};
+use serde::de::{self, Deserialize, Deserializer};
+use serde_json::{Map, Value};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fmt::{self, Display, Formatter, Write as _},
+    str::FromStr,
+};
+
+/// EIP-712 typed data.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```
+/// # use ethaddr::eip712::TypedData;
+/// let typed_data = r#"{
+///     "types": {
+///         "EIP712Domain": [
+///             { "name": "name", "type": "string" },
+///             { "name": "version", "type": "string" },
+///             { "name": "chainId", "type": "uint256" },
+///             { "name": "verifyingContract", "type": "address" }
+///         ],
+///         "Person": [
+///             { "name": "name", "type": "string" },
+///             { "name": "wallet", "type": "address" }
+///         ],
+///         "Mail": [
+///             { "name": "from", "type": "Person" },
+///             { "name": "to", "type": "Person" },
+///             { "name": "contents", "type": "string" }
+///         ]
+///     },
+///     "primaryType": "Mail",
+///     "domain": {
+///         "name": "Ether Mail",
+///         "version": "1",
+///         "chainId": 1,
+///         "verifyingContract": "0xCcCCccccCCCCcCCCCCCcCcCccCcCCCcCcccccccC"
+///     },
+///     "message": {
+///         "from": {
+///             "name": "Cow",
+///             "wallet": "0xCD2a3d9F938E13CD947Ec05AbC7FE734Df8DD826"
+///         },
+///         "to": {
+///             "name": "Bob",
+///             "wallet": "0xbBbBBBBbbBBBbbbBbbBbbbbBBbBbbbbBbBbbBBbB"
+///         },
+///         "contents": "Hello, Bob!"
+///     }
+/// }"#
+/// .parse::<TypedData>()
+/// .unwrap();
+/// assert_eq!(
+///     typed_data.signing_hash(),
+///     [
+///         0xbe, 0x60, 0x9a, 0xee, 0x34, 0x3f, 0xb3, 0xc4, 0xb2, 0x8e, 0x1d,
+///         0xf9, 0xe6, 0x32, 0xfc, 0xa6, 0x4f, 0xcf, 0xae, 0xde, 0x20, 0xf0,
+///         0x2e, 0x86, 0x24, 0x4e, 0xfd, 0xdf, 0x30, 0x95, 0x7b, 0xd2,
+///     ],
+/// );
+/// ```
+#[derive(Clone, Debug)]
+pub struct TypedData {
+    types: BTreeMap<String, Vec<Member>>,
+    primary_type: String,
+    domain: Map<String, Value>,
+    message: Map<String, Value>,
+    domain_separator: [u8; 32],
+    struct_hash: Option<[u8; 32]>,
+}
+
+/// A member of an EIP-712 struct type.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Member {
+    /// The member name.
+    pub name: String,
+    /// The member type, for example `uint256`, `Person` or `address[]`.
+    pub ty: String,
+}
+
+/// The name of the EIP-712 domain struct type.
+const DOMAIN_TYPE: &str = "EIP712Domain";
+
+/// The domain members in their canonical order along with their type. This
+/// is used for inferring the domain type when it is not explicitly specified.
+const DOMAIN_MEMBERS: [(&str, &str); 5] = [
+    ("name", "string"),
+    ("version", "string"),
+    ("chainId", "uint256"),
+    ("verifyingContract", "address"),
+    ("salt", "bytes32"),
+];
+
+impl TypedData {
+    /// Creates typed data from a JSON value.
+    pub fn from_value(value: Value) -> Result<Self, TypedDataError> {
+        let mut value = match value {
+            Value::Object(value) => value,
+            _ => return Err(TypedDataError::InvalidField("typed data")),
+        };
+        let mut field = |name| value.remove(name).ok_or(TypedDataError::MissingField(name));
+
+        let types = match field("types")? {
+            Value::Object(types) => types
+                .into_iter()
+                .map(|(name, members)| Ok((name, parse_members(members)?)))
+                .collect::<Result<BTreeMap<_, _>, TypedDataError>>()?,
+            _ => return Err(TypedDataError::InvalidField("types")),
+        };
+        let primary_type = match field("primaryType")? {
+            Value::String(primary_type) => primary_type,
+            _ => return Err(TypedDataError::InvalidField("primaryType")),
+        };
+        let domain = match field("domain")? {
+            Value::Object(domain) => domain,
+            _ => return Err(TypedDataError::InvalidField("domain")),
+        };
+        let message = match field("message")? {
+            Value::Object(message) => message,
+            _ => return Err(TypedDataError::InvalidField("message")),
+        };
+
+        Self::new(types, primary_type, domain, message)
+    }
+
+    /// Creates typed data from its components.
+    ///
+    /// If the `types` do not include an `EIP712Domain` type, then it is
+    /// inferred from the members present in the `domain`.
+    pub fn new(
+        mut types: BTreeMap<String, Vec<Member>>,
+        primary_type: String,
+        domain: Map<String, Value>,
+        message: Map<String, Value>,
+    ) -> Result<Self, TypedDataError> {
+        types.entry(DOMAIN_TYPE.to_owned()).or_insert_with(|| {
+            DOMAIN_MEMBERS
+                .iter()
+                .filter(|(name, _)| domain.contains_key(*name))
+                .map(|(name, ty)| Member {
+                    name: (*name).to_owned(),
+                    ty: (*ty).to_owned(),
+                })
+                .collect()
+        });
+
+        let encoder = Encoder { types: &types };
+        for member in types.values().flatten() {
+            encoder.check_type(&member.ty)?;
+        }
+        if !types.contains_key(&primary_type) {
+            return Err(TypedDataError::UnknownType(primary_type));
+        }
+
+        let domain_separator = encoder.hash_struct(DOMAIN_TYPE, &domain)?;
+        let struct_hash = if primary_type != DOMAIN_TYPE {
+            Some(encoder.hash_struct(&primary_type, &message)?)
+        } else {
+            None
+        };
+
+        Ok(Self {
+            types,
+            primary_type,
+            domain,
+            message,
+            domain_separator,
+            struct_hash,
+        })
+    }
+
+    /// Returns the struct types, including the `EIP712Domain` type.
+    pub fn types(&self) -> &BTreeMap<String, Vec<Member>> {
+        &self.types
+    }
+
+    /// Returns the name of the primary struct type of the message.
+    pub fn primary_type(&self) -> &str {
+        &self.primary_type
+    }
+
+    /// Returns the domain values.
+    pub fn domain(&self) -> &Map<String, Value> {
+        &self.domain
+    }
+
+    /// Returns the message values.
+    pub fn message(&self) -> &Map<String, Value> {
+        &self.message
+    }
+
+    /// Returns the EIP-712 domain separator.
+    pub fn domain_separator(&self) -> [u8; 32] {
+        self.domain_separator
+    }
+
+    /// Returns the EIP-712 struct hash of the message.
+    ///
+    /// Returns `None` if the primary type is `EIP712Domain`, in which case the
+    /// message is not included in the signing hash.
+    pub fn struct_hash(&self) -> Option<[u8; 32]> {
+        self.struct_hash
+    }
+
+    /// Returns the EIP-712 signing hash.
+    ///
+    /// This is the Keccak-256 hash of
+    /// `0x19 ++ 0x01 ++ domainSeparator ++ hashStruct(message)`.
+    pub fn signing_hash(&self) -> [u8; 32] {
+        checksum::keccak256_concat(&[
+            &[0x19, 0x01],
+            &self.domain_separator,
+            self.struct_hash
+                .as_ref()
+                .map(|hash| &hash[..])
+                .unwrap_or(&[]),
+        ])
+    }
+
+    /// Recovers the signer address of an EIP-712 signature.
+    ///
+    /// See [`Address::recover`] for the supported signature encodings.
+    pub fn recover(&self, signature: &[u8]) -> Result<Address, RecoverError> {
+        Address::recover(self.signing_hash(), signature)
+    }
+}
+
+impl FromStr for TypedData {
+    type Err = TypedDataError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::from_value(serde_json::from_str(s).map_err(TypedDataError::Json)?)
+    }
+}
+
+impl<'de> Deserialize<'de> for TypedData {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        Self::from_value(Value::deserialize(deserializer)?).map_err(de::Error::custom)
+    }
+}
+
+/// Parses the members of a struct type.
+fn parse_members(members: Value) -> Result<Vec<Member>, TypedDataError> {
+    let members = match members {
+        Value::Array(members) => members,
+        _ => return Err(TypedDataError::InvalidField("types")),
+    };
+    members
+        .into_iter()
+        .map(|member| {
+            let string = |name| {
+                member
+                    .get(name)
+                    .and_then(Value::as_str)
+                    .map(str::to_owned)
+                    .ok_or(TypedDataError::InvalidField("types"))
+            };
+            Ok(Member {
+                name: string("name")?,
+                ty: string("type")?,
+            })
+        })
+        .collect()
+}
+
+/// EIP-712 data encoder.
+struct Encoder<'a> {
+    types: &'a BTreeMap<String, Vec<Member>>,
+}
+
+impl Encoder<'_> {
+    /// Checks that a type is valid.
+    fn check_type(&self, ty: &str) -> Result<(), TypedDataError> {
+        if let Some((inner, _)) = array_type(ty)? {
+            return self.check_type(inner);
+        }
+        if self.types.contains_key(ty) || atomic_type(ty).is_some() {
+            return Ok(());
+        }
+        Err(TypedDataError::UnknownType(ty.to_owned()))
+    }
+
+    /// Encodes a struct type with all of its dependencies.
+    fn encode_type(&self, name: &str) -> String {
+        let mut dependencies = BTreeSet::new();
+        self.dependencies(name, &mut dependencies);
+        dependencies.remove(name);
+
+        let mut encoded = String::new();
+        for name in [name].into_iter().chain(dependencies) {
+            let members = &self.types[name];
+            write!(encoded, "{name}(").unwrap();
+            for (i, member) in members.iter().enumerate() {
+                if i > 0 {
+                    encoded.push(',');
+                }
+                write!(encoded, "{} {}", member.ty, member.name).unwrap();
+            }
+            encoded.push(')');
+        }
+        encoded
+    }
+
+    /// Collects all struct type dependencies of a struct type.
+    fn dependencies<'a>(&'a self, name: &'a str, dependencies: &mut BTreeSet<&'a str>) {
+        if !dependencies.insert(name) {
+            return;
+        }
+        for member in &self.types[name] {
+            let mut ty = member.ty.as_str();
+            while let Ok(Some((inner, _))) = array_type(ty) {
+                ty = inner;
+            }
+            if self.types.contains_key(ty) {
+                self.dependencies(ty, dependencies);
+            }
+        }
+    }
+
+    /// Computes the EIP-712 struct hash of some data.
+    fn hash_struct(
+        &self,
+        name: &str,
+        data: &Map<String, Value>,
+    ) -> Result<[u8; 32], TypedDataError> {
+        let members = &self.types[name];
+        let mut encoded = Vec::with_capacity(32 * (members.len() + 1));
+        encoded.extend(checksum::keccak256(self.encode_type(name).as_bytes()));
+        for member in members {
+            let value = data
+                .get(&member.name)
+                .ok_or_else(|| TypedDataError::MissingValue(member.name.clone()))?;
+            encoded.extend(self.encode_value(&member.ty, value)?);
+        }
+        Ok(checksum::keccak256(&encoded))
+    }
+
+    /// Encodes a single value as a 32-byte word.
+    fn encode_value(&self, ty: &str, value: &Value) -> Result<[u8; 32], TypedDataError> {
+        let invalid = || TypedDataError::InvalidValue(ty.to_owned());
+
+        if let Some((inner, len)) = array_type(ty)? {
+            let items = value.as_array().ok_or_else(invalid)?;
+            if len.is_some_and(|len| len != items.len()) {
+                return Err(invalid());
+            }
+            let mut encoded = Vec::with_capacity(32 * items.len());
+            for item in items {
+                encoded.extend(self.encode_value(inner, item)?);
+            }
+            return Ok(checksum::keccak256(&encoded));
+        }
+        if self.types.contains_key(ty) {
+            return self.hash_struct(ty, value.as_object().ok_or_else(invalid)?);
+        }
+
+        let mut word = [0_u8; 32];
+        match atomic_type(ty).ok_or_else(invalid)? {
+            Atomic::Address => {
+                let address = Address::from_str_lenient(value.as_str().ok_or_else(invalid)?)
+                    .map_err(TypedDataError::InvalidAddress)?;
+                word[12..].copy_from_slice(address.as_slice());
+            }
+            Atomic::Bool => word[31] = value.as_bool().ok_or_else(invalid)? as _,
+            Atomic::String => {
+                word = checksum::keccak256(value.as_str().ok_or_else(invalid)?.as_bytes())
+            }
+            Atomic::Bytes => {
+                let bytes = value.as_str().and_then(decode_hex).ok_or_else(invalid)?;
+                word = checksum::keccak256(&bytes);
+            }
+            Atomic::FixedBytes(len) => {
+                let bytes = value
+                    .as_str()
+                    .and_then(decode_hex)
+                    .filter(|bytes| bytes.len() == len)
+                    .ok_or_else(invalid)?;
+                word[..len].copy_from_slice(&bytes);
+            }
+            Atomic::Uint(bits) => {
+                let (negative, magnitude) = parse_integer(value).ok_or_else(invalid)?;
+                if (negative && magnitude != [0; 32]) || bit_len(&magnitude) > bits {
+                    return Err(invalid());
+                }
+                word = magnitude;
+            }
+            Atomic::Int(bits) => {
+                let (negative, magnitude) = parse_integer(value).ok_or_else(invalid)?;
+                let len = bit_len(&magnitude);
+                // The minimum value `-2^(bits-1)` has a magnitude with a bit
+                // length of `bits` where only the most significant bit is set.
+                let min = len == bits && magnitude.iter().map(|b| b.count_ones()).sum::<u32>() == 1;
+                if len >= bits && !(negative && min) {
+                    return Err(invalid());
+                }
+                word = if negative {
+                    negate(magnitude)
+                } else {
+                    magnitude
+                };
+            }
+        }
+        Ok(word)
+    }
+}
+
+/// Atomic EIP-712 types.
+enum Atomic {
+    Address,
+    Bool,
+    String,
+    Bytes,
+    FixedBytes(usize),
+    Uint(usize),
+    Int(usize),
+}
+
+/// Parses an atomic or dynamic type.
+fn atomic_type(ty: &str) -> Option<Atomic> {
+    let bits = |n: &str| {
+        let bits = n.parse::<usize>().ok()?;
+        (bits > 0 && bits <= 256 && bits % 8 == 0 && !n.starts_with('0')).then_some(bits)
+    };
+    match ty {
+        "address" => Some(Atomic::Address),
+        "bool" => Some(Atomic::Bool),
+        "string" => Some(Atomic::String),
+        "bytes" => Some(Atomic::Bytes),
+        _ => {
+            if let Some(n) = ty.strip_prefix("bytes") {
+                let len = n.parse::<usize>().ok()?;
+                (len > 0 && len <= 32 && !n.starts_with('0')).then_some(Atomic::FixedBytes(len))
+            } else if let Some(n) = ty.strip_prefix("uint") {
+                bits(n).map(Atomic::Uint)
+            } else if let Some(n) = ty.strip_prefix("int") {
+                bits(n).map(Atomic::Int)
+            } else {
+                None
+            }
+        }
+    }
+}
+
+/// Parses an array type into its inner type and optional fixed length.
+fn array_type(ty: &str) -> Result<Option<(&str, Option<usize>)>, TypedDataError> {
+    let Some(ty_prefix) = ty.strip_suffix(']') else {
+        return Ok(None);
+    };
+    let (inner, len) = ty_prefix
+        .rsplit_once('[')
+        .ok_or_else(|| TypedDataError::UnknownType(ty.to_owned()))?;
+    let len = match len {
+        "" => None,
+        len => Some(
+            len.parse()
+                .map_err(|_| TypedDataError::UnknownType(ty.to_owned()))?,
+        ),
+    };
+    Ok(Some((inner, len)))
+}
+
+/// Decodes a `0x`-prefixed hex string.
+fn decode_hex(s: &str) -> Option<Vec<u8>> {
+    let s = s.strip_prefix("0x")?;
+    if s.len() % 2 != 0 {
+        return None;
+    }
+    (0..s.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(s.get(i..i + 2)?, 16).ok())
+        .collect()
+}
+
+/// Parses an integer value into its sign and 256-bit big-endian magnitude.
+///
+/// Integers can be specified as JSON numbers, or as decimal or `0x`-prefixed
+/// hexadecimal strings with an optional `-` sign.
+fn parse_integer(value: &Value) -> Option<(bool, [u8; 32])> {
+    let mut magnitude = [0_u8; 32];
+    match value {
+        Value::Number(number) => {
+            let (negative, n) = match (number.as_u64(), number.as_i64()) {
+                (Some(n), _) => (false, n),
+                (None, Some(n)) => (true, n.unsigned_abs()),
+                _ => return None,
+            };
+            magnitude[24..].copy_from_slice(&n.to_be_bytes());
+            Some((negative, magnitude))
+        }
+        Value::String(s) => {
+            let (negative, s) = match s.strip_prefix('-') {
+                Some(s) => (true, s),
+                None => (false, s.as_str()),
+            };
+            let (radix, digits) = match s.strip_prefix("0x") {
+                Some(digits) => (16, digits),
+                None => (10, s),
+            };
+            if digits.is_empty() {
+                return None;
+            }
+            for digit in digits.chars() {
+                let mut carry = digit.to_digit(radix)?;
+                for byte in magnitude.iter_mut().rev() {
+                    let value = (*byte as u32) * radix + carry;
+                    *byte = value as u8;
+                    carry = value >> 8;
+                }
+                if carry != 0 {
+                    return None;
+                }
+            }
+            Some((negative, magnitude))
+        }
+        _ => None,
+    }
+}
+
+/// Returns the number of significant bits in a 256-bit big-endian integer.
+fn bit_len(value: &[u8; 32]) -> usize {
+    match value.iter().position(|byte| *byte != 0) {
+        Some(i) => (32 - i) * 8 - value[i].leading_zeros() as usize,
+        None => 0,
+    }
+}
+
+/// Computes the two's complement negation of a 256-bit big-endian integer.
+fn negate(mut value: [u8; 32]) -> [u8; 32] {
+    let mut carry = 1;
+    for byte in value.iter_mut().rev() {
+        let (sum, overflow) = (!*byte).overflowing_add(carry);
+        *byte = sum;
+        carry = overflow as u8;
+    }
+    value
+}
+
+/// Represents an error parsing or hashing EIP-712 typed data.
+#[derive(Debug)]
+pub enum TypedDataError {
+    /// The typed data is not valid JSON.
+    Json(serde_json::Error),
+    /// A required typed data field is missing.
+    MissingField(&'static str),
+    /// A typed data field has an invalid format.
+    InvalidField(&'static str),
+    /// A type is not a valid EIP-712 type or a known struct type.
+    UnknownType(String),
+    /// A struct member value is missing.
+    MissingValue(String),
+    /// A value is not valid for its type.
+    InvalidValue(String),
+    /// An address value is invalid.
+    InvalidAddress(ParseAddressError),
+}
+
+impl Display for TypedDataError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Self::Json(err) => write!(f, "invalid typed data JSON: {err}"),
+            Self::MissingField(field) => write!(f, "missing typed data field `{field}`"),
+            Self::InvalidField(field) => write!(f, "invalid typed data field `{field}`"),
+            Self::UnknownType(ty) => write!(f, "unknown type `{ty}`"),
+            Self::MissingValue(member) => write!(f, "missing value for member `{member}`"),
+            Self::InvalidValue(ty) => write!(f, "invalid value for type `{ty}`"),
+            Self::InvalidAddress(err) => write!(f, "invalid address value: {err}"),
+        }
+    }
+}
+
+impl std::error::Error for TypedDataError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Json(err) => Some(err),
+            Self::InvalidAddress(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::bytes;
+    use serde_json::json;
+
+    fn mail() -> Value {
+        json!({
+            "types": {
+                "EIP712Domain": [
+                    { "name": "name", "type": "string" },
+                    { "name": "version", "type": "string" },
+                    { "name": "chainId", "type": "uint256" },
+                    { "name": "verifyingContract", "type": "address" },
+                ],
+                "Person": [
+                    { "name": "name", "type": "string" },
+                    { "name": "wallet", "type": "address" },
+                ],
+                "Mail": [
+                    { "name": "from", "type": "Person" },
+                    { "name": "to", "type": "Person" },
+                    { "name": "contents", "type": "string" },
+                ],
+            },
+            "primaryType": "Mail",
+            "domain": {
+                "name": "Ether Mail",
+                "version": "1",
+                "chainId": 1,
+                "verifyingContract": "0xCcCCccccCCCCcCCCCCCcCcCccCcCCCcCcccccccC",
+            },
+            "message": {
+                "from": {
+                    "name": "Cow",
+                    "wallet": "0xCD2a3d9F938E13CD947Ec05AbC7FE734Df8DD826",
+                },
+                "to": {
+                    "name": "Bob",
+                    "wallet": "0xbBbBBBBbbBBBbbbBbbBbbbbBBbBbbbbBbBbbBBbB",
+                },
+                "contents": "Hello, Bob!",
+            },
+        })
+    }
+
+    #[test]
+    fn eip712_mail_example() {
+        let typed_data = TypedData::from_value(mail()).unwrap();
+
+        assert_eq!(
+            typed_data.encoder().encode_type("Mail"),
+            "Mail(Person from,Person to,string contents)Person(string name,address wallet)",
+        );
+        assert_eq!(
+            typed_data.domain_separator()[..],
+            bytes("0xf2cee375fa42b42143804025fc449deafd50cc031ca257e0b194a650a912090f"),
+        );
+        assert_eq!(
+            typed_data.struct_hash().unwrap()[..],
+            bytes("0xc52c0ee5d84264471806290a3f2c4cecfc5490626bf912d01f240d7a274b371e"),
+        );
+        assert_eq!(
+            typed_data.signing_hash()[..],
+            bytes("0xbe609aee343fb3c4b28e1df9e632fca64fcfaede20f02e86244efddf30957bd2"),
+        );
+
+        let signature = bytes(
+            "0x4355c47d63924e8a72e509b65029052eb6c299d53a04e167c5775fd466751c9d\
+               07299936d304c153f6443dfa05f40ff007d72911b6f72307f996231605b91562\
+               1c",
+        );
+        assert_eq!(
+            typed_data.recover(&signature).unwrap(),
+            Address::from_str_checksum("0xCD2a3d9F938E13CD947Ec05AbC7FE734Df8DD826").unwrap(),
+        );
+    }
+
+    #[test]
+    fn infers_domain_type() {
+        let mut value = mail();
+        value["types"].as_object_mut().unwrap().remove(DOMAIN_TYPE);
+        let typed_data = TypedData::from_value(value).unwrap();
+
+        assert_eq!(
+            typed_data.domain_separator(),
+            TypedData::from_value(mail()).unwrap().domain_separator(),
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_addresses() {
+        let mut value = mail();
+        value["domain"]["verifyingContract"] = json!("0xCcCCccccCCCCcCCCCCCcCcCccCcCCCcCcccccccc");
+        assert!(matches!(
+            TypedData::from_value(value),
+            Err(TypedDataError::InvalidAddress(
+                ParseAddressError::ChecksumMismatch { .. }
+            )),
+        ));
+
+        let mut value = mail();
+        value["message"]["to"]["wallet"] = json!("0xbbbb");
+        assert!(matches!(
+            TypedData::from_value(value),
+            Err(TypedDataError::InvalidAddress(
+                ParseAddressError::InvalidLength {
+                    len: 4,
+                    prefixed: true,
+                }
+            )),
+        ));
+
+        let mut value = mail();
+        value["message"]["to"]["wallet"] = json!("0xbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbb");
+        assert!(TypedData::from_value(value).is_ok());
+    }
+
+    #[test]
+    fn rejects_invalid_types_and_values() {
+        let mut value = mail();
+        value["types"]["Person"][0]["type"] = json!("uint7");
+        assert!(matches!(
+            TypedData::from_value(value),
+            Err(TypedDataError::UnknownType(ty)) if ty == "uint7",
+        ));
+
+        let mut value = mail();
+        value["message"]["from"]
+            .as_object_mut()
+            .unwrap()
+            .remove("name");
+        assert!(matches!(
+            TypedData::from_value(value),
+            Err(TypedDataError::MissingValue(member)) if member == "name",
+        ));
+
+        let mut value = mail();
+        value["domain"]["chainId"] = json!("-1");
+        assert!(matches!(
+            TypedData::from_value(value),
+            Err(TypedDataError::InvalidValue(ty)) if ty == "uint256",
+        ));
+    }
+
+    #[test]
+    fn encode_integers() {
+        let encoder = Encoder {
+            types: &BTreeMap::new(),
+        };
+        let word = |ty, value| encoder.encode_value(ty, &value).ok().map(|w| w.to_vec());
+        let hex = |s| Some(bytes(s));
+
+        assert_eq!(
+            word("uint8", json!(255)),
+            hex("0x00000000000000000000000000000000000000000000000000000000000000ff"),
+        );
+        assert_eq!(word("uint8", json!(256)), None);
+        assert_eq!(
+            word("uint256", json!("0x0100")),
+            hex("0x0000000000000000000000000000000000000000000000000000000000000100"),
+        );
+        assert_eq!(
+            word(
+                "uint256",
+                json!("115792089237316195423570985008687907853269984665640564039457584007913129639935"),
+            ),
+            hex("0xffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff"),
+        );
+        assert_eq!(
+            word(
+                "uint256",
+                json!("115792089237316195423570985008687907853269984665640564039457584007913129639936"),
+            ),
+            None,
+        );
+        assert_eq!(
+            word("int8", json!(-128)),
+            hex("0xffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff80"),
+        );
+        assert_eq!(word("int8", json!(-129)), None);
+        assert_eq!(word("int8", json!(128)), None);
+        assert_eq!(
+            word("int16", json!("-1")),
+            hex("0xffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff"),
+        );
+        assert_eq!(
+            word("bytes4", json!("0xdeadbeef")),
+            hex("0xdeadbeef00000000000000000000000000000000000000000000000000000000"),
+        );
+        assert_eq!(word("bytes4", json!("0xdead")), None);
+    }
+
+    impl TypedData {
+        fn encoder(&self) -> Encoder<'_> {
+            Encoder { types: &self.types }
+        }
+    }
+}
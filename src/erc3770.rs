@@ -0,0 +1,251 @@
+//! ERC-3770 chain-specific addresses.
+//!
+//! ERC-3770 addresses prefix an address with the short name of the chain it
+//! belongs to, for example `eth:0x...` for Ethereum mainnet or `arb1:0x...`
+//! for Arbitrum One. Short names are resolved to EIP-155 chain IDs with a
+//! table generated at build time from a curated subset of the
+//! [chain list](https://github.com/ethereum-lists/chains) committed to the
+//! repository in `data/chains.csv`, so only popular chains are known.
+
+use crate::{caip::ChainAccount, Address, ParseAddressError, Parser, Prefix};
+use core::{
+    fmt::{self, Display, Formatter},
+    str::FromStr,
+};
+
+include!(concat!(env!("OUT_DIR"), "/short_names.rs"));
+
+/// Returns the EIP-155 chain ID for a short name, or `None` if the short name
+/// is unknown.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```
+/// # use ethaddr::erc3770;
+/// assert_eq!(erc3770::chain_id("eth"), Some(1));
+/// assert_eq!(erc3770::chain_id("arb1"), Some(42161));
+/// assert_eq!(erc3770::chain_id("unknown"), None);
+/// ```
+pub fn chain_id(short_name: &str) -> Option<u64> {
+    let i = BY_SHORT_NAME
+        .binary_search_by(|(entry, _)| (*entry).cmp(short_name))
+        .ok()?;
+    Some(BY_SHORT_NAME[i].1)
+}
+
+/// Returns the short name for an EIP-155 chain ID, or `None` if the chain ID
+/// is unknown.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```
+/// # use ethaddr::erc3770;
+/// assert_eq!(erc3770::short_name(10), Some("oeth"));
+/// assert_eq!(erc3770::short_name(0), None);
+/// ```
+pub fn short_name(chain_id: u64) -> Option<&'static str> {
+    let i = BY_CHAIN_ID
+        .binary_search_by_key(&chain_id, |(entry, _)| *entry)
+        .ok()?;
+    Some(BY_CHAIN_ID[i].1)
+}
+
+/// An ERC-3770 chain-specific address.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```
+/// # use ethaddr::{erc3770::ChainAddress, Address};
+/// let address = "gno:0xeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeee"
+///     .parse::<ChainAddress>()
+///     .unwrap();
+/// assert_eq!(address.short_name(), "gno");
+/// assert_eq!(
+///     <(u64, Address)>::from(address),
+///     (100, Address([0xee; 20])),
+/// );
+/// ```
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct ChainAddress {
+    short_name: &'static str,
+    chain_id: u64,
+    address: Address,
+}
+
+impl ChainAddress {
+    /// Creates a new chain-specific address for an EIP-155 chain ID. Returns
+    /// an error if there is no known short name for the chain.
+    pub fn new(chain_id: u64, address: Address) -> Result<Self, ChainAddressError> {
+        Ok(Self {
+            short_name: short_name(chain_id).ok_or(ChainAddressError::UnknownChainId(chain_id))?,
+            chain_id,
+            address,
+        })
+    }
+
+    /// Returns the chain short name.
+    pub fn short_name(&self) -> &'static str {
+        self.short_name
+    }
+
+    /// Returns the EIP-155 chain ID.
+    pub fn chain_id(&self) -> u64 {
+        self.chain_id
+    }
+
+    /// Returns the address.
+    pub fn address(&self) -> Address {
+        self.address
+    }
+}
+
+impl Display for ChainAddress {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "{}:{}", self.short_name, self.address)
+    }
+}
+
+impl FromStr for ChainAddress {
+    type Err = ChainAddressError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (short_name, address) = s.split_once(':').ok_or(ChainAddressError::InvalidFormat)?;
+        let chain_id = chain_id(short_name).ok_or(ChainAddressError::UnknownShortName)?;
+        Self::new(
+            chain_id,
+            Parser::new()
+                .prefix(Prefix::Required)
+                .parse(address)
+                .map_err(ChainAddressError::Address)?,
+        )
+    }
+}
+
+impl From<ChainAddress> for (u64, Address) {
+    fn from(address: ChainAddress) -> Self {
+        (address.chain_id, address.address)
+    }
+}
+
+impl TryFrom<(u64, Address)> for ChainAddress {
+    type Error = ChainAddressError;
+
+    fn try_from((chain_id, address): (u64, Address)) -> Result<Self, Self::Error> {
+        Self::new(chain_id, address)
+    }
+}
+
+impl From<ChainAddress> for ChainAccount {
+    fn from(address: ChainAddress) -> Self {
+        ChainAccount::eip155(address.chain_id, address.address)
+    }
+}
+
+/// Represents an error parsing or creating an ERC-3770 chain-specific
+/// address.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ChainAddressError {
+    /// The address is not of the form `short_name:address`.
+    InvalidFormat,
+    /// The chain short name is not known.
+    UnknownShortName,
+    /// There is no known short name for the chain ID.
+    UnknownChainId(u64),
+    /// The address is invalid.
+    Address(ParseAddressError),
+}
+
+impl Display for ChainAddressError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Self::InvalidFormat => write!(f, "address is not of the form `short_name:address`"),
+            Self::UnknownShortName => write!(f, "unknown chain short name"),
+            Self::UnknownChainId(chain_id) => write!(f, "unknown short name for chain {chain_id}"),
+            Self::Address(err) => write!(f, "invalid address: {err}"),
+        }
+    }
+}
+
+impl core::error::Error for ChainAddressError {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        match self {
+            Self::Address(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn short_name_table() {
+        assert!(BY_SHORT_NAME.windows(2).all(|w| w[0].0 < w[1].0));
+        assert!(BY_CHAIN_ID.windows(2).all(|w| w[0].0 < w[1].0));
+        for (short_name, chain_id) in BY_SHORT_NAME {
+            assert_eq!(self::chain_id(short_name), Some(chain_id));
+            assert_eq!(self::short_name(chain_id), Some(short_name));
+        }
+    }
+
+    #[test]
+    fn chain_addresses() {
+        for (s, chain_id) in [
+            ("eth:0xEeeeeEeeeEeEeeEeEeEeeEEEeeeeEeeeeeeeEEeE", 1),
+            ("oeth:0xEeeeeEeeeEeEeeEeEeEeeEEEeeeeEeeeeeeeEEeE", 10),
+            ("arb1:0xEeeeeEeeeEeEeeEeEeEeeEEEeeeeEeeeeeeeEEeE", 42161),
+            ("arb-nova:0xEeeeeEeeeEeEeeEeEeEeeEEEeeeeEeeeeeeeEEeE", 42170),
+        ] {
+            let address = s.parse::<ChainAddress>().unwrap();
+            assert_eq!(address.chain_id(), chain_id);
+            assert_eq!(address.address(), Address([0xee; 20]));
+            assert_eq!(
+                ChainAddress::try_from((chain_id, Address([0xee; 20]))).unwrap(),
+                address,
+            );
+
+            #[cfg(feature = "checksum")]
+            assert_eq!(address.to_string(), s);
+        }
+    }
+
+    #[test]
+    fn invalid_chain_addresses() {
+        assert_eq!(
+            "0xEeeeeEeeeEeEeeEeEeEeeEEEeeeeEeeeeeeeEEeE".parse::<ChainAddress>(),
+            Err(ChainAddressError::InvalidFormat),
+        );
+        assert_eq!(
+            "ETH:0xEeeeeEeeeEeEeeEeEeEeeEEEeeeeEeeeeeeeEEeE".parse::<ChainAddress>(),
+            Err(ChainAddressError::UnknownShortName),
+        );
+        assert!(matches!(
+            "eth:0x1234".parse::<ChainAddress>(),
+            Err(ChainAddressError::Address(_)),
+        ));
+        assert_eq!(
+            "eth:EeeeeEeeeEeEeeEeEeEeeEEEeeeeEeeeeeeeEEeE".parse::<ChainAddress>(),
+            Err(ChainAddressError::Address(ParseAddressError::MissingPrefix)),
+        );
+        assert_eq!(
+            ChainAddress::new(0, Address([0xee; 20])),
+            Err(ChainAddressError::UnknownChainId(0)),
+        );
+    }
+
+    #[test]
+    fn chain_account_conversion() {
+        let address = ChainAddress::new(100, Address([0xee; 20])).unwrap();
+        assert_eq!(
+            ChainAccount::from(address),
+            ChainAccount::eip155(100, Address([0xee; 20])),
+        );
+    }
+}
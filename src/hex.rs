@@ -1,66 +1,662 @@
 //! Internal module used for hex-string parsing.
 
+#[cfg(feature = "checksum")]
+use crate::buffer::{Checksum, FormattingBuffer};
+use crate::keccak;
+#[cfg(feature = "checksum")]
+use core::str;
 use core::{
-    fmt::{self, Display, Formatter},
+    fmt::{self, Debug, Display, Formatter},
     mem::{self, MaybeUninit},
 };
 
 /// Decode a hex string into address bytes.
 pub fn decode(s: &str) -> Result<[u8; 20], ParseAddressError> {
-    let (s, ch_offset) = match s.strip_prefix("0x") {
-        Some(s) => (s, 2),
-        None => (s, 0),
+    Parser::new().decode(s)
+}
+
+/// Decode a hex string into address bytes in a `const` context.
+///
+/// # Panics
+///
+/// This function panics if the hex string is not a valid address, which
+/// results in a compilation error when evaluated at compile time.
+pub const fn decode_const(s: &str) -> [u8; 20] {
+    let s = match s.as_bytes() {
+        [b'0', b'x', rest @ ..] => rest,
+        s => s,
     };
     if s.len() != 40 {
-        return Err(ParseAddressError::InvalidLength);
+        panic!("invalid address hex string length");
+    }
+
+    const fn nibble(c: u8) -> u8 {
+        match c {
+            b'0'..=b'9' => c - b'0',
+            b'A'..=b'F' => c - b'A' + 0xa,
+            b'a'..=b'f' => c - b'a' + 0xa,
+            _ => panic!("invalid address hex character"),
+        }
+    }
+
+    let mut bytes = [0; 20];
+    let mut i = 0;
+    while i < 20 {
+        bytes[i] = (nibble(s[i * 2]) << 4) + nibble(s[i * 2 + 1]);
+        i += 1;
     }
+    bytes
+}
 
-    let mut bytes = [MaybeUninit::<u8>::uninit(); 20];
-    let nibble = |c| match c {
-        b'0'..=b'9' => Some(c - b'0'),
-        b'A'..=b'F' => Some(c - b'A' + 0xa),
-        b'a'..=b'f' => Some(c - b'a' + 0xa),
-        _ => None,
+/// Decode a checksummed hex string into address bytes in a `const` context.
+///
+/// # Panics
+///
+/// This function panics if the hex string is not a valid address or if its
+/// checksum does not match, which results in a compilation error when
+/// evaluated at compile time.
+pub const fn decode_checksum_const(s: &str) -> [u8; 20] {
+    let bytes = decode_const(s);
+    let digits = match s.as_bytes() {
+        [b'0', b'x', rest @ ..] => rest,
+        s => s,
     };
-    let invalid_char = |i: usize| ParseAddressError::InvalidHexCharacter {
-        c: s[i..].chars().next().unwrap(),
-        index: i + ch_offset,
+
+    let mut lower = [0; 40];
+    let mut i = 0;
+    while i < lower.len() {
+        lower[i] = digits[i].to_ascii_lowercase();
+        i += 1;
+    }
+
+    let digest = keccak::keccak256(&lower);
+    let mut i = 0;
+    while i < lower.len() {
+        let byte = digest[i / 2];
+        let nibble = 0xf & if i % 2 == 0 { byte >> 4 } else { byte };
+        let expected = if nibble >= 8 {
+            lower[i].to_ascii_uppercase()
+        } else {
+            lower[i]
+        };
+        if digits[i] != expected {
+            panic!("invalid address checksum");
+        }
+        i += 1;
+    }
+    bytes
+}
+
+/// A configurable parser for Ethereum public addresses.
+///
+/// The default parser accepts 40 hex digits with an optional lowercase `0x`
+/// prefix, and ignores address checksums; this is the same behaviour as the
+/// `FromStr` implementation for addresses.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Parser {
+    prefix: Prefix,
+    uppercase_prefix: bool,
+    trim: bool,
+    #[cfg(feature = "checksum")]
+    checksum: ChecksumPolicy,
+    short: bool,
+    padded: bool,
+    normalize: bool,
+}
+
+/// Whether or not the `0x` prefix is expected when parsing an address.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Prefix {
+    /// The prefix may or may not be present.
+    Optional,
+    /// The prefix must be present.
+    Required,
+    /// The prefix must not be present.
+    Forbidden,
+}
+
+/// The checksum verification policy when parsing an address.
+#[cfg(feature = "checksum")]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ChecksumPolicy {
+    /// The case of the address hex digits is ignored.
+    Ignore,
+    /// The address must have a valid EIP-55 checksum.
+    Required,
+    /// The address must have a valid EIP-55 checksum only if it is
+    /// mixed-case. This is the policy commonly used by wallets.
+    MixedCase,
+}
+
+impl Parser {
+    /// Creates a new parser with the default options.
+    pub const fn new() -> Self {
+        Self {
+            prefix: Prefix::Optional,
+            uppercase_prefix: false,
+            trim: false,
+            #[cfg(feature = "checksum")]
+            checksum: ChecksumPolicy::Ignore,
+            short: false,
+            padded: false,
+            normalize: false,
+        }
+    }
+
+    /// Sets whether or not the `0x` prefix is required, optional or
+    /// forbidden.
+    pub const fn prefix(mut self, prefix: Prefix) -> Self {
+        self.prefix = prefix;
+        self
+    }
+
+    /// Sets whether or not to accept the uppercase `0X` prefix.
+    pub const fn uppercase_prefix(mut self, enabled: bool) -> Self {
+        self.uppercase_prefix = enabled;
+        self
+    }
+
+    /// Sets whether or not surrounding whitespace and quotes are trimmed from
+    /// the input before parsing.
+    pub const fn trim(mut self, enabled: bool) -> Self {
+        self.trim = enabled;
+        self
+    }
+
+    /// Sets the checksum verification policy.
+    #[cfg(feature = "checksum")]
+    pub const fn checksum(mut self, policy: ChecksumPolicy) -> Self {
+        self.checksum = policy;
+        self
+    }
+
+    /// Sets whether or not to accept short Solidity-style literals, such as
+    /// `0x1`, which get left-padded with zeros.
+    ///
+    /// Note that checksums are verified on the left-padded address string.
+    pub const fn short(mut self, enabled: bool) -> Self {
+        self.short = enabled;
+        self
+    }
+
+    /// Sets whether or not to accept 32-byte words left-padded with zeros, as
+    /// addresses are encoded in the Ethereum ABI.
+    pub const fn padded(mut self, enabled: bool) -> Self {
+        self.padded = enabled;
+        self
+    }
+
+    /// Sets whether or not to normalize common Unicode confusables to the hex
+    /// digits they were likely intended to be, and remove invisible
+    /// characters, instead of rejecting them.
+    ///
+    /// Note that when normalizing, error indices are still byte offsets in
+    /// the input string, while lengths count the characters of the normalized
+    /// string. Inputs longer than 255 bytes are rejected.
+    pub const fn normalize(mut self, enabled: bool) -> Self {
+        self.normalize = enabled;
+        self
+    }
+
+    /// Decodes a hex string into address bytes.
+    pub(crate) fn decode(&self, s: &str) -> Result<[u8; 20], ParseAddressError> {
+        let mut offset = 0;
+        let mut s = s;
+        if self.trim {
+            let trimmed = s.trim_start_matches(is_trimmed);
+            offset += s.len() - trimmed.len();
+            s = trimmed.trim_end_matches(is_trimmed);
+        }
+
+        if self.normalize {
+            let mut buffer = [0; NORMALIZED_LEN];
+            let mut indices = [0; NORMALIZED_LEN];
+            let normalized = self.normalized(s, offset, &mut buffer, &mut indices)?;
+            self.decode_trimmed(normalized, 0)
+                .map_err(|err| err.map_indices(|i| offset + indices[i] as usize))
+        } else {
+            // Only look for confusables once decoding fails, so that errors
+            // for multi-byte characters name the offending character instead
+            // of being reported as unhelpful length errors.
+            self.decode_trimmed(s, offset).map_err(|err| match err {
+                ParseAddressError::InvalidLength { .. }
+                | ParseAddressError::InvalidHexCharacter { .. } => {
+                    self.confusable(s, offset).unwrap_or(err)
+                }
+                err => err,
+            })
+        }
+    }
+
+    /// Normalizes confusable and invisible characters of an address string
+    /// into the specified buffer, recording the byte offset in `s` of each
+    /// normalized character in `indices`.
+    fn normalized<'a>(
+        &self,
+        s: &str,
+        offset: usize,
+        buffer: &'a mut [u8; NORMALIZED_LEN],
+        indices: &mut [u8; NORMALIZED_LEN],
+    ) -> Result<&'a str, ParseAddressError> {
+        // Limit the input length so that byte offsets fit in a `u8`, which is
+        // how offsets of checksum mismatches are stored.
+        if s.len() > u8::MAX as usize {
+            let prefixed = s.starts_with("0x") || (self.uppercase_prefix && s.starts_with("0X"));
+            return Err(ParseAddressError::InvalidLength {
+                len: s.len() - if prefixed { 2 } else { 0 },
+                prefixed,
+            });
+        }
+
+        let mut len = 0;
+        for (i, c) in s.char_indices() {
+            if is_invisible(c) {
+                continue;
+            }
+            let c = intended_digit(c).unwrap_or(c);
+            if !c.is_ascii() {
+                return Err(ParseAddressError::InvalidHexCharacter {
+                    c,
+                    index: i + offset,
+                });
+            }
+            if let Some(b) = buffer.get_mut(len) {
+                *b = c as u8;
+                indices[len] = i as u8;
+            }
+            len += 1;
+        }
+
+        if len > buffer.len() {
+            let prefixed = match buffer {
+                [b'0', b'x', ..] => true,
+                [b'0', b'X', ..] => self.uppercase_prefix,
+                _ => false,
+            };
+            return Err(ParseAddressError::InvalidLength {
+                len: len - if prefixed { 2 } else { 0 },
+                prefixed,
+            });
+        }
+
+        // SAFETY: The buffer only contains ASCII characters.
+        Ok(unsafe { core::str::from_utf8_unchecked(&buffer[..len]) })
+    }
+
+    /// Returns an error for the first character in an address string that is
+    /// not a hex digit, if it is a confusable or invisible character.
+    fn confusable(&self, s: &str, offset: usize) -> Option<ParseAddressError> {
+        let start = match s.as_bytes() {
+            [b'0', b'x', ..] => 2,
+            [b'0', b'X', ..] if self.uppercase_prefix => 2,
+            _ => 0,
+        };
+        let (i, c) = s[start..]
+            .char_indices()
+            .find(|(_, c)| !c.is_ascii_hexdigit())?;
+        let index = start + i + offset;
+        if is_invisible(c) {
+            Some(ParseAddressError::InvisibleCharacter { c, index })
+        } else {
+            let intended = intended_digit(c)?;
+            Some(ParseAddressError::ConfusableCharacter { c, index, intended })
+        }
+    }
+
+    /// Decodes a trimmed hex string into address bytes.
+    fn decode_trimmed(&self, s: &str, offset: usize) -> Result<[u8; 20], ParseAddressError> {
+        let mut offset = offset;
+        let mut s = s;
+        let prefixed = match s.as_bytes() {
+            [b'0', b'x', ..] => true,
+            [b'0', b'X', ..] => self.uppercase_prefix,
+            _ => false,
+        };
+        match (self.prefix, prefixed) {
+            (Prefix::Required, false) => return Err(ParseAddressError::MissingPrefix),
+            (Prefix::Forbidden, true) => return Err(ParseAddressError::UnexpectedPrefix),
+            (_, true) => {
+                s = &s[2..];
+                offset += 2;
+            }
+            _ => {}
+        }
+
+        let nibble = |c| match c {
+            b'0'..=b'9' => Some(c - b'0'),
+            b'A'..=b'F' => Some(c - b'A' + 0xa),
+            b'a'..=b'f' => Some(c - b'a' + 0xa),
+            _ => None,
+        };
+        let digits = match s.len() {
+            40 => s.as_bytes(),
+            64 if self.padded => {
+                for (i, c) in s.bytes().take(24).enumerate() {
+                    match nibble(c) {
+                        Some(0) => {}
+                        Some(_) => return Err(ParseAddressError::InvalidPadding),
+                        None => return Err(invalid_char(s, i, offset)),
+                    }
+                }
+                s = &s[24..];
+                offset += 24;
+                s.as_bytes()
+            }
+            1..=39 if self.short => s.as_bytes(),
+            len => return Err(ParseAddressError::InvalidLength { len, prefixed }),
+        };
+
+        // Left-pad the hex digits with zeros to the full address length.
+        let mut buffer = [b'0'; 40];
+        let padding = buffer.len() - digits.len();
+        buffer[padding..].copy_from_slice(digits);
+
+        let mut bytes = [MaybeUninit::<u8>::uninit(); 20];
+        for (i, ch) in buffer.chunks(2).enumerate() {
+            let invalid = |j: usize| invalid_char(s, j - padding, offset);
+            let hi = nibble(ch[0]).ok_or_else(|| invalid(i * 2))?;
+            let lo = nibble(ch[1]).ok_or_else(|| invalid(i * 2 + 1))?;
+            bytes[i].write((hi << 4) + lo);
+        }
+
+        let bytes = unsafe { mem::transmute::<[MaybeUninit<u8>; 20], [u8; 20]>(bytes) };
+
+        #[cfg(feature = "checksum")]
+        {
+            let mixed_case = digits.iter().any(u8::is_ascii_lowercase)
+                && digits.iter().any(u8::is_ascii_uppercase);
+            let verify = match self.checksum {
+                ChecksumPolicy::Ignore => false,
+                ChecksumPolicy::Required => true,
+                ChecksumPolicy::MixedCase => mixed_case,
+            };
+            if verify {
+                // SAFETY: The buffer only contains valid hex digits.
+                let checksum = unsafe { str::from_utf8_unchecked(&buffer) };
+                crate::checksum::verify(&bytes, checksum).map_err(|expected| {
+                    // SAFETY: The digits are valid hex digits.
+                    let digits = unsafe { str::from_utf8_unchecked(digits) };
+                    ParseAddressError::checksum_mismatch(expected, digits, offset)
+                })?;
+            }
+        }
+
+        Ok(bytes)
+    }
+}
+
+/// Returns `true` if the character is trimmed from the start and end of
+/// address strings: whitespace and quotes.
+pub fn is_trimmed(c: char) -> bool {
+    c.is_whitespace() || c == '"' || c == '\''
+}
+
+/// The maximum length of a normalized address string: a `0x` prefix followed
+/// by a 32-byte padded word.
+const NORMALIZED_LEN: usize = 66;
+
+/// Returns the hex digit that a character was likely intended to be, if the
+/// character is a common confusable (homoglyph) of a hex digit.
+fn intended_digit(c: char) -> Option<char> {
+    let digit = match c {
+        // Latin, Greek and Cyrillic letters `O`.
+        'O' | 'o' | '\u{39f}' | '\u{3bf}' | '\u{41e}' | '\u{43e}' => '0',
+        // Latin letters `I` and `l`.
+        'I' | 'l' => '1',
+        // Fullwidth digits and letters.
+        '\u{ff10}'..='\u{ff19}' => char::from(b'0' + (c as u32 - 0xff10) as u8),
+        '\u{ff21}'..='\u{ff26}' => char::from(b'A' + (c as u32 - 0xff21) as u8),
+        '\u{ff41}'..='\u{ff46}' => char::from(b'a' + (c as u32 - 0xff41) as u8),
+        // Greek and Cyrillic capital letters.
+        '\u{391}' | '\u{410}' => 'A',
+        '\u{392}' | '\u{412}' => 'B',
+        '\u{3f9}' | '\u{421}' => 'C',
+        '\u{395}' | '\u{415}' => 'E',
+        // Cyrillic small letters.
+        '\u{430}' => 'a',
+        '\u{44c}' => 'b',
+        '\u{441}' => 'c',
+        '\u{501}' => 'd',
+        '\u{435}' => 'e',
+        _ => return None,
     };
+    Some(digit)
+}
 
-    for (i, ch) in s.as_bytes().chunks(2).enumerate() {
-        let hi = nibble(ch[0]).ok_or_else(|| invalid_char(i * 2))?;
-        let lo = nibble(ch[1]).ok_or_else(|| invalid_char(i * 2 + 1))?;
-        bytes[i].write((hi << 4) + lo);
+/// Returns `true` if the character is invisible.
+fn is_invisible(c: char) -> bool {
+    matches!(
+        c,
+        '\u{ad}' | '\u{200b}'..='\u{200f}' | '\u{2060}'..='\u{2064}' | '\u{feff}'
+    )
+}
+
+/// Returns an invalid hex character error for the character at the specified
+/// byte index of a string, offset from the start of the original input.
+fn invalid_char(s: &str, i: usize, offset: usize) -> ParseAddressError {
+    ParseAddressError::InvalidHexCharacter {
+        c: s[i..].chars().next().unwrap(),
+        index: i + offset,
     }
+}
 
-    let bytes = unsafe { mem::transmute(bytes) };
-    Ok(bytes)
+impl Default for Parser {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 /// Represents an error parsing an address from a string.
 #[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
 pub enum ParseAddressError {
     /// The hex string does not have the correct length.
-    InvalidLength,
-    /// An invalid character was found.
+    InvalidLength {
+        /// The length in bytes of the hex string, excluding any `0x` prefix.
+        len: usize,
+        /// Whether or not the hex string had a `0x` prefix.
+        prefixed: bool,
+    },
+    /// An invalid character was found. The index is the byte offset of the
+    /// character in the input string.
     InvalidHexCharacter { c: char, index: usize },
     /// The checksum encoded in the hex string's case does not match the
     /// address.
-    #[allow(dead_code)]
-    ChecksumMismatch,
+    #[cfg(feature = "checksum")]
+    ChecksumMismatch {
+        /// The expected checksummed address string.
+        expected: Checksum,
+        /// The byte offset of the first hex digit in the input string.
+        index: usize,
+        /// The byte offsets in the input string of the hex digits whose case
+        /// does not match the expected checksum.
+        mismatches: MismatchedIndices,
+    },
+    /// The `0x` prefix is required but missing.
+    MissingPrefix,
+    /// The `0x` prefix is forbidden but present.
+    UnexpectedPrefix,
+    /// A left-padded 32-byte word has non-zero padding.
+    InvalidPadding,
+    /// A character that is commonly confused with a hex digit was found.
+    ConfusableCharacter {
+        c: char,
+        index: usize,
+        /// The hex digit that was likely intended.
+        intended: char,
+    },
+    /// An invisible character, such as a zero-width space, was found.
+    InvisibleCharacter { c: char, index: usize },
+}
+
+impl ParseAddressError {
+    /// Maps the byte offsets in the error with the specified function.
+    fn map_indices(self, f: impl Fn(usize) -> usize) -> Self {
+        match self {
+            Self::InvalidHexCharacter { c, index } => {
+                Self::InvalidHexCharacter { c, index: f(index) }
+            }
+            #[cfg(feature = "checksum")]
+            Self::ChecksumMismatch {
+                expected,
+                index,
+                mismatches,
+            } => Self::ChecksumMismatch {
+                expected,
+                index: f(index),
+                mismatches: MismatchedIndices::new(f(index), mismatches.iter().map(&f)),
+            },
+            Self::ConfusableCharacter { c, index, intended } => Self::ConfusableCharacter {
+                c,
+                index: f(index),
+                intended,
+            },
+            Self::InvisibleCharacter { c, index } => {
+                Self::InvisibleCharacter { c, index: f(index) }
+            }
+            err => err,
+        }
+    }
+}
+
+#[cfg(feature = "checksum")]
+impl ParseAddressError {
+    /// Creates a checksum mismatch error from the expected checksummed address
+    /// and the hex digits that were parsed, starting at byte offset `index`
+    /// of the input string.
+    ///
+    /// Short hex strings are compared against the end of the expected
+    /// checksum, since they get left-padded with zeros.
+    pub(crate) fn checksum_mismatch(
+        expected: FormattingBuffer,
+        digits: &str,
+        index: usize,
+    ) -> Self {
+        let expected_digits = expected.as_bytes_str();
+        let padding = expected_digits.len().saturating_sub(digits.len());
+        let mismatches = expected_digits[padding..]
+            .bytes()
+            .zip(digits.bytes())
+            .enumerate()
+            .filter(|(_, (a, b))| a != b)
+            .map(|(i, _)| index + i);
+        Self::ChecksumMismatch {
+            expected: Checksum(expected),
+            index,
+            mismatches: MismatchedIndices::new(index, mismatches),
+        }
+    }
+
+    /// Returns the byte offsets in the input string of the hex digits whose
+    /// case does not match the expected checksum.
+    ///
+    /// This is empty for errors other than
+    /// [`ParseAddressError::ChecksumMismatch`].
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// # use ethaddr::Address;
+    /// let err = Address::from_str_checksum("0xEeeeeEeeeEeEeeEeEeEeeEEEeeeeEeeeeeeeEEEe")
+    ///     .unwrap_err();
+    /// assert_eq!(err.mismatched_indices().collect::<Vec<_>>(), [40, 41]);
+    /// ```
+    pub fn mismatched_indices(&self) -> impl Iterator<Item = usize> {
+        let mismatches = match self {
+            Self::ChecksumMismatch { mismatches, .. } => *mismatches,
+            _ => MismatchedIndices::new(0, []),
+        };
+        mismatches.iter()
+    }
+}
+
+/// The byte offsets in an input string of the hex digits whose case does not
+/// match the expected checksum.
+#[cfg(feature = "checksum")]
+#[derive(Clone, Copy, Eq, Hash, PartialEq)]
+pub struct MismatchedIndices {
+    index: usize,
+    offsets: [u8; 40],
+    len: u8,
+}
+
+#[cfg(feature = "checksum")]
+impl MismatchedIndices {
+    /// Creates a new set of mismatched indices from increasing byte offsets,
+    /// none of which are more than 255 bytes past `index`. There are at most
+    /// 40 mismatched hex digits.
+    pub(crate) fn new(index: usize, indices: impl IntoIterator<Item = usize>) -> Self {
+        let mut offsets = [0; 40];
+        let mut len = 0;
+        for (offset, i) in offsets.iter_mut().zip(indices) {
+            *offset = (i - index) as u8;
+            len += 1;
+        }
+        Self {
+            index,
+            offsets,
+            len,
+        }
+    }
+
+    /// Returns an iterator over the byte offsets in the input string.
+    pub fn iter(self) -> impl Iterator<Item = usize> {
+        (0..self.len as usize).map(move |i| self.index + self.offsets[i] as usize)
+    }
+
+    /// Returns the number of mismatched hex digits.
+    pub fn len(&self) -> usize {
+        self.len as _
+    }
+
+    /// Returns `true` if there are no mismatched hex digits.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+}
+
+#[cfg(feature = "checksum")]
+impl Debug for MismatchedIndices {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
 }
 
 impl Display for ParseAddressError {
     fn fmt(&self, f: &mut Formatter) -> fmt::Result {
         match self {
-            Self::InvalidLength { .. } => write!(f, "invalid hex string length"),
+            Self::InvalidLength { len, prefixed } => {
+                let prefix = if *prefixed { " after `0x` prefix" } else { "" };
+                write!(
+                    f,
+                    "invalid hex string length {len}{prefix}, expected 40 hex digits"
+                )
+            }
             Self::InvalidHexCharacter { c, index } => {
                 write!(f, "invalid character `{c}` at position {index}")
             }
-            Self::ChecksumMismatch => write!(f, "address checksum does not match"),
+            #[cfg(feature = "checksum")]
+            Self::ChecksumMismatch { expected, .. } => {
+                write!(f, "address checksum does not match, expected `{expected}`")
+            }
+            Self::MissingPrefix => write!(f, "missing `0x` prefix"),
+            Self::UnexpectedPrefix => write!(f, "unexpected `0x` prefix"),
+            Self::InvalidPadding => write!(f, "non-zero address padding"),
+            Self::ConfusableCharacter { c, index, intended } => write!(
+                f,
+                "suspicious character `{c}` (U+{:04X}) at position {index}; \
+                 did you mean `{intended}`?",
+                *c as u32,
+            ),
+            Self::InvisibleCharacter { c, index } => write!(
+                f,
+                "invisible character U+{:04X} at position {index}",
+                *c as u32,
+            ),
         }
     }
 }
 
-#[cfg(feature = "std")]
-impl std::error::Error for ParseAddressError {}
+impl core::error::Error for ParseAddressError {}
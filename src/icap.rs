@@ -0,0 +1,361 @@
+//! ICAP (Inter exchange Client Address Protocol) encoding of addresses.
+//!
+//! ICAP is an IBAN-compatible encoding of Ethereum addresses using the `XE`
+//! country code. Addresses are encoded in base-36 as the BBAN (Basic Bank
+//! Account Number), either in the 30-character "direct" form (for addresses
+//! smaller than 2^155) or in the 31-character "basic" form. The "indirect"
+//! form, which encodes an institution and client identifier to be resolved by
+//! a name registry, is not supported.
+
+use crate::Address;
+use core::{
+    fmt::{self, Debug, Display, Formatter},
+    str,
+};
+
+/// The length of the BBAN of direct ICAP addresses.
+const DIRECT_LEN: usize = 30;
+
+/// The length of the BBAN of basic ICAP addresses.
+const BASIC_LEN: usize = 31;
+
+/// The length of the BBAN of indirect ICAP addresses.
+const INDIRECT_LEN: usize = 16;
+
+/// Base-36 alphabet.
+const ALPHABET: &[u8; 36] = b"0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZ";
+
+impl Address {
+    /// Encodes the address in the direct ICAP form. Returns an error if the
+    /// address is too large to be represented in the direct form, in which
+    /// case [`Address::to_icap_basic`] can be used instead.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// # use ethaddr::{Address, IcapError};
+    /// let address = "0x00c5496aee77c1ba1f0854206a26dda82a81d6d8"
+    ///     .parse::<Address>()
+    ///     .unwrap();
+    /// assert_eq!(
+    ///     address.to_icap().unwrap().as_str(),
+    ///     "XE7338O073KYGTWWZN0F2WZ0R8PX5ZPPZS",
+    /// );
+    /// assert_eq!(Address([0xee; 20]).to_icap(), Err(IcapError::NotDirect));
+    /// ```
+    pub fn to_icap(&self) -> Result<Icap, IcapError> {
+        if !is_direct(self) {
+            return Err(IcapError::NotDirect);
+        }
+        // Values smaller than 2^155 always fit in the direct form.
+        Ok(encode(self, DIRECT_LEN).unwrap())
+    }
+
+    /// Encodes the address in the basic ICAP form.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// # use ethaddr::Address;
+    /// let icap = Address([0xee; 20]).to_icap_basic();
+    /// assert_eq!(icap.as_str().len(), 35);
+    /// assert_eq!(Address::from_icap(icap.as_str()).unwrap(), Address([0xee; 20]));
+    /// ```
+    pub fn to_icap_basic(&self) -> Icap {
+        // The basic form can represent any 160-bit value.
+        encode(self, BASIC_LEN).unwrap()
+    }
+
+    /// Decodes an address from a direct or basic ICAP string.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// # use ethaddr::Address;
+    /// let address = Address::from_icap("XE7338O073KYGTWWZN0F2WZ0R8PX5ZPPZS").unwrap();
+    /// assert_eq!(
+    ///     address,
+    ///     Address::from_hex("0x00c5496aee77c1ba1f0854206a26dda82a81d6d8"),
+    /// );
+    /// ```
+    pub fn from_icap(s: &str) -> Result<Self, IcapError> {
+        let bban_len = match s.len() {
+            len if len == 4 + DIRECT_LEN || len == 4 + BASIC_LEN || len == 4 + INDIRECT_LEN => {
+                len - 4
+            }
+            _ => return Err(IcapError::InvalidLength),
+        };
+
+        let bytes = s.as_bytes();
+        let invalid_char = |i: usize| IcapError::InvalidCharacter {
+            c: s[i..].chars().next().unwrap(),
+            index: i,
+        };
+        if !bytes[..2].eq_ignore_ascii_case(b"XE") {
+            return Err(IcapError::InvalidCountryCode);
+        }
+        if let Some(i) = (2..4).find(|i| !bytes[*i].is_ascii_digit()) {
+            return Err(invalid_char(i));
+        }
+        if let Some(i) = (4..bytes.len()).find(|i| digit(bytes[*i]).is_none()) {
+            return Err(invalid_char(i));
+        }
+
+        let bban = &bytes[4..];
+        if checksum(&[bban, &bytes[..4]]) != 1 {
+            return Err(IcapError::ChecksumMismatch);
+        }
+        if bban_len == INDIRECT_LEN {
+            return Err(IcapError::Indirect);
+        }
+
+        let mut address = Address::default();
+        for c in &bban[..bban_len] {
+            // Multiply the address by 36 and add the digit as a big-endian
+            // 160-bit integer, detecting overflows.
+            let mut carry = digit(*c).unwrap() as u32;
+            for byte in address.iter_mut().rev() {
+                let value = *byte as u32 * 36 + carry;
+                *byte = value as u8;
+                carry = value >> 8;
+            }
+            if carry != 0 {
+                return Err(IcapError::Overflow);
+            }
+        }
+        if bban_len == DIRECT_LEN && !is_direct(&address) {
+            return Err(IcapError::Overflow);
+        }
+
+        Ok(address)
+    }
+}
+
+/// An ICAP encoded address.
+#[derive(Clone, Copy, Eq, Hash, PartialEq)]
+pub struct Icap {
+    buffer: [u8; 4 + BASIC_LEN],
+    len: usize,
+}
+
+impl Icap {
+    /// Returns the ICAP string.
+    pub fn as_str(&self) -> &str {
+        // SAFETY: Buffer only ever contains ASCII characters.
+        unsafe { str::from_utf8_unchecked(&self.buffer[..self.len]) }
+    }
+
+    /// Returns `true` if the ICAP is in the direct form.
+    pub fn is_direct(&self) -> bool {
+        self.len == 4 + DIRECT_LEN
+    }
+}
+
+impl Debug for Icap {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.debug_tuple("Icap").field(&self.as_str()).finish()
+    }
+}
+
+impl Display for Icap {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.pad(self.as_str())
+    }
+}
+
+/// Returns `true` if the address can be encoded in the direct ICAP form, that
+/// is, if it is smaller than 2^155.
+fn is_direct(address: &Address) -> bool {
+    address.0[0] < 0x08
+}
+
+/// Encodes an address as an ICAP with a BBAN of the specified length. Returns
+/// `None` if the address does not fit.
+fn encode(address: &Address, bban_len: usize) -> Option<Icap> {
+    let mut buffer = [0; 4 + BASIC_LEN];
+    buffer[..4].copy_from_slice(b"XE00");
+
+    // Repeatedly divide the address by 36 as a big-endian 160-bit integer,
+    // writing the remainders as base-36 digits from right to left.
+    let mut value = address.0;
+    for i in (4..4 + bban_len).rev() {
+        let mut remainder = 0_u32;
+        for byte in value.iter_mut() {
+            let acc = (remainder << 8) + *byte as u32;
+            *byte = (acc / 36) as u8;
+            remainder = acc % 36;
+        }
+        buffer[i] = ALPHABET[remainder as usize];
+    }
+    if value != [0; 20] {
+        return None;
+    }
+
+    let len = 4 + bban_len;
+    let check = 98 - checksum(&[&buffer[4..len], b"XE00"]);
+    buffer[2] = b'0' + check / 10;
+    buffer[3] = b'0' + check % 10;
+
+    Some(Icap { buffer, len })
+}
+
+/// Returns the value of a base-36 digit.
+fn digit(c: u8) -> Option<u8> {
+    match c {
+        b'0'..=b'9' => Some(c - b'0'),
+        b'A'..=b'Z' => Some(c - b'A' + 10),
+        b'a'..=b'z' => Some(c - b'a' + 10),
+        _ => None,
+    }
+}
+
+/// Computes the ISO 7064 mod-97 remainder of the concatenation of the
+/// specified alphanumeric parts, where letters are expanded to two digits.
+fn checksum(parts: &[&[u8]]) -> u8 {
+    let mut remainder = 0_u32;
+    for c in parts.iter().flat_map(|part| part.iter()) {
+        let value = digit(*c).unwrap() as u32;
+        let scale = if value < 10 { 10 } else { 100 };
+        remainder = (remainder * scale + value) % 97;
+    }
+    remainder as _
+}
+
+/// Represents an error encoding or decoding an ICAP address.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum IcapError {
+    /// The ICAP string does not have the length of a direct or basic ICAP.
+    InvalidLength,
+    /// The ICAP string does not have the `XE` country code.
+    InvalidCountryCode,
+    /// An invalid character was found.
+    InvalidCharacter { c: char, index: usize },
+    /// The ICAP check digits do not match.
+    ChecksumMismatch,
+    /// The ICAP string is in the unsupported indirect form.
+    Indirect,
+    /// The ICAP string encodes a value that is too large for its form: 2^155
+    /// or larger for direct ICAP strings, or larger than 160 bits for basic
+    /// ICAP strings.
+    Overflow,
+    /// The address is too large to be encoded in the direct ICAP form.
+    NotDirect,
+}
+
+impl Display for IcapError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Self::InvalidLength => write!(f, "invalid ICAP length"),
+            Self::InvalidCountryCode => write!(f, "invalid ICAP country code"),
+            Self::InvalidCharacter { c, index } => {
+                write!(f, "invalid ICAP character `{c}` at position {index}")
+            }
+            Self::ChecksumMismatch => write!(f, "ICAP check digits do not match"),
+            Self::Indirect => write!(f, "indirect ICAP addresses are not supported"),
+            Self::Overflow => write!(f, "ICAP value does not fit in an address"),
+            Self::NotDirect => write!(f, "address cannot be encoded as a direct ICAP"),
+        }
+    }
+}
+
+impl core::error::Error for IcapError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn direct_icap() {
+        let address = "0x00c5496aee77c1ba1f0854206a26dda82a81d6d8"
+            .parse::<Address>()
+            .unwrap();
+        let icap = address.to_icap().unwrap();
+        assert!(icap.is_direct());
+        assert_eq!(icap.as_str(), "XE7338O073KYGTWWZN0F2WZ0R8PX5ZPPZS");
+        assert_eq!(Address::from_icap(icap.as_str()).unwrap(), address);
+        assert_eq!(
+            Address::from_icap("xe7338o073kygtwwzn0f2wz0r8px5zppzs").unwrap(),
+            address,
+        );
+    }
+
+    #[test]
+    fn direct_icap_limit() {
+        // Direct ICAP addresses are limited to values smaller than 2^155, even
+        // though 30 base-36 digits can represent values up to 36^30 - 1.
+        let mut largest = Address([0xff; 20]);
+        largest[0] = 0x07;
+        let direct = largest.to_icap().unwrap();
+        assert!(direct.is_direct());
+        assert_eq!(Address::from_icap(direct.as_str()).unwrap(), largest);
+
+        let mut smallest = Address::default();
+        smallest[0] = 0x08;
+        assert_eq!(smallest.to_icap(), Err(IcapError::NotDirect));
+        let basic = smallest.to_icap_basic();
+        assert!(!basic.is_direct());
+        assert_eq!(Address::from_icap(basic.as_str()).unwrap(), smallest);
+
+        let overflow = encode(&smallest, DIRECT_LEN).unwrap();
+        assert_eq!(
+            Address::from_icap(overflow.as_str()),
+            Err(IcapError::Overflow),
+        );
+    }
+
+    #[test]
+    fn basic_icap_roundtrip() {
+        for address in [Address::default(), Address([0x42; 20]), Address([0xff; 20])] {
+            let icap = address.to_icap_basic();
+            assert_eq!(icap.as_str().len(), 35);
+            assert_eq!(Address::from_icap(icap.as_str()).unwrap(), address);
+        }
+    }
+
+    #[test]
+    fn invalid_icap() {
+        for (s, err) in [
+            (
+                "XE7338O073KYGTWWZN0F2WZ0R8PX5ZPPZ",
+                IcapError::InvalidLength,
+            ),
+            (
+                "DE7338O073KYGTWWZN0F2WZ0R8PX5ZPPZS",
+                IcapError::InvalidCountryCode,
+            ),
+            (
+                "XE7338O073KYGTWWZN0F2WZ0R8PX5ZPPZT",
+                IcapError::ChecksumMismatch,
+            ),
+            (
+                "XE7338O073KYGTWWZN0F2WZ0R8PX5ZPP-S",
+                IcapError::InvalidCharacter { c: '-', index: 32 },
+            ),
+            ("XE81ETHXREGGAVOFYORK", IcapError::Indirect),
+            ("DE81ETHXREGGAVOFYORK", IcapError::InvalidCountryCode),
+            ("XE82ETHXREGGAVOFYORK", IcapError::ChecksumMismatch),
+            (
+                "XE81ETH-REGGAVOFYORK",
+                IcapError::InvalidCharacter { c: '-', index: 7 },
+            ),
+        ] {
+            assert_eq!(Address::from_icap(s), Err(err));
+        }
+
+        // Basic ICAP values larger than 160 bits.
+        let mut overflow = *b"XE00ZZZZZZZZZZZZZZZZZZZZZZZZZZZZZZZ";
+        let check = 98 - checksum(&[&overflow[4..], b"XE00"]);
+        overflow[2] = b'0' + check / 10;
+        overflow[3] = b'0' + check % 10;
+        assert_eq!(
+            Address::from_icap(str::from_utf8(&overflow).unwrap()),
+            Err(IcapError::Overflow),
+        );
+    }
+}
@@ -0,0 +1,224 @@
+//! Built-in `const` implementation of the Keccak-256 hash function.
+//!
+//! This is a straightforward implementation of the Keccak-f[1600] permutation
+//! with the original Keccak padding (as used by Ethereum, and not the SHA-3
+//! padding). It is not as fast as optimized implementations, but it has no
+//! dependencies and can be evaluated at compile time.
+
+/// The rate in bytes of Keccak-256.
+const RATE: usize = 136;
+
+/// Keccak-f[1600] round constants.
+const RC: [u64; 24] = [
+    0x0000000000000001,
+    0x0000000000008082,
+    0x800000000000808a,
+    0x8000000080008000,
+    0x000000000000808b,
+    0x0000000080000001,
+    0x8000000080008081,
+    0x8000000000008009,
+    0x000000000000008a,
+    0x0000000000000088,
+    0x0000000080008009,
+    0x000000008000000a,
+    0x000000008000808b,
+    0x800000000000008b,
+    0x8000000000008089,
+    0x8000000000008003,
+    0x8000000000008002,
+    0x8000000000000080,
+    0x000000000000800a,
+    0x800000008000000a,
+    0x8000000080008081,
+    0x8000000000008080,
+    0x0000000080000001,
+    0x8000000080008008,
+];
+
+/// Keccak-f[1600] rotation offsets for the combined ρ and π steps.
+const ROTC: [u32; 24] = [
+    1, 3, 6, 10, 15, 21, 28, 36, 45, 55, 2, 14, 27, 41, 56, 8, 25, 43, 62, 18, 39, 61, 20, 44,
+];
+
+/// Keccak-f[1600] lane indices for the π step.
+const PILN: [usize; 24] = [
+    10, 7, 11, 17, 18, 3, 5, 16, 8, 21, 24, 4, 15, 23, 19, 13, 12, 2, 20, 14, 22, 9, 6, 1,
+];
+
+/// Perform Keccak-256 hash over some input bytes.
+pub const fn keccak256(bytes: &[u8]) -> [u8; 32] {
+    let mut hasher = Keccak256::new();
+    hasher.update(bytes);
+    hasher.finalize()
+}
+
+/// A streaming Keccak-256 hasher.
+pub struct Keccak256 {
+    state: [u64; 25],
+    offset: usize,
+}
+
+impl Keccak256 {
+    /// Creates a new hasher.
+    pub const fn new() -> Self {
+        Self {
+            state: [0; 25],
+            offset: 0,
+        }
+    }
+
+    /// Absorbs input bytes into the hasher.
+    pub const fn update(&mut self, bytes: &[u8]) {
+        let mut i = 0;
+        while i < bytes.len() {
+            self.xor(self.offset, bytes[i]);
+            self.offset += 1;
+            if self.offset == RATE {
+                keccak_f(&mut self.state);
+                self.offset = 0;
+            }
+            i += 1;
+        }
+    }
+
+    /// Pads the input and returns the resulting hash.
+    pub const fn finalize(mut self) -> [u8; 32] {
+        self.xor(self.offset, 0x01);
+        self.xor(RATE - 1, 0x80);
+        keccak_f(&mut self.state);
+
+        let mut digest = [0; 32];
+        let mut i = 0;
+        while i < digest.len() {
+            digest[i] = (self.state[i / 8] >> (8 * (i % 8))) as u8;
+            i += 1;
+        }
+        digest
+    }
+
+    /// XORs a byte into the state at the specified byte offset.
+    const fn xor(&mut self, offset: usize, byte: u8) {
+        self.state[offset / 8] ^= (byte as u64) << (8 * (offset % 8));
+    }
+}
+
+/// The Keccak-f[1600] permutation.
+const fn keccak_f(a: &mut [u64; 25]) {
+    let mut round = 0;
+    while round < 24 {
+        // θ step.
+        let mut c = [0; 5];
+        let mut x = 0;
+        while x < 5 {
+            c[x] = a[x] ^ a[x + 5] ^ a[x + 10] ^ a[x + 15] ^ a[x + 20];
+            x += 1;
+        }
+        let mut x = 0;
+        while x < 5 {
+            let d = c[(x + 4) % 5] ^ c[(x + 1) % 5].rotate_left(1);
+            let mut y = 0;
+            while y < 25 {
+                a[y + x] ^= d;
+                y += 5;
+            }
+            x += 1;
+        }
+
+        // ρ and π steps.
+        let mut last = a[1];
+        let mut i = 0;
+        while i < 24 {
+            let j = PILN[i];
+            let tmp = a[j];
+            a[j] = last.rotate_left(ROTC[i]);
+            last = tmp;
+            i += 1;
+        }
+
+        // χ step.
+        let mut y = 0;
+        while y < 25 {
+            let row = [a[y], a[y + 1], a[y + 2], a[y + 3], a[y + 4]];
+            let mut x = 0;
+            while x < 5 {
+                a[y + x] = row[x] ^ (!row[(x + 1) % 5] & row[(x + 2) % 5]);
+                x += 1;
+            }
+            y += 5;
+        }
+
+        // ι step.
+        a[0] ^= RC[round];
+        round += 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn known_hashes() {
+        const EMPTY: [u8; 32] = keccak256(b"");
+        assert_eq!(
+            EMPTY,
+            [
+                0xc5, 0xd2, 0x46, 0x01, 0x86, 0xf7, 0x23, 0x3c, 0x92, 0x7e, 0x7d, 0xb2, 0xdc, 0xc7,
+                0x03, 0xc0, 0xe5, 0x00, 0xb6, 0x53, 0xca, 0x82, 0x27, 0x3b, 0x7b, 0xfa, 0xd8, 0x04,
+                0x5d, 0x85, 0xa4, 0x70,
+            ],
+        );
+        assert_eq!(
+            keccak256(b"abc"),
+            [
+                0x4e, 0x03, 0x65, 0x7a, 0xea, 0x45, 0xa9, 0x4f, 0xc7, 0xd4, 0x7b, 0xa8, 0x26, 0xc8,
+                0xd6, 0x67, 0xc0, 0xd1, 0xe6, 0xe3, 0x3a, 0x64, 0xa0, 0x36, 0xec, 0x44, 0xf5, 0x8f,
+                0xa1, 0x2d, 0x6c, 0x45,
+            ],
+        );
+    }
+
+    #[cfg(feature = "sha3")]
+    #[test]
+    fn equivalent_to_sha3() {
+        use sha3::{Digest as _, Keccak256 as Sha3Keccak256};
+
+        let input = (0..1000_u32)
+            .map(|i| (i.wrapping_mul(0x9e37_79b9) >> 24) as u8)
+            .collect::<Vec<_>>();
+
+        // Cover lengths around multiples of the rate, where padding bytes
+        // cross block boundaries.
+        for len in 0..=3 * RATE + 1 {
+            let expected: [u8; 32] = Sha3Keccak256::digest(&input[..len]).into();
+            assert_eq!(keccak256(&input[..len]), expected);
+        }
+
+        for split in [1, 7, 135, 136, 137, 500] {
+            let mut hasher = Keccak256::new();
+            for chunk in input.chunks(split) {
+                hasher.update(chunk);
+            }
+            let expected: [u8; 32] = Sha3Keccak256::digest(&input).into();
+            assert_eq!(hasher.finalize(), expected);
+        }
+    }
+
+    #[cfg(feature = "tiny-keccak")]
+    #[test]
+    fn equivalent_to_tiny_keccak() {
+        use tiny_keccak::{Hasher as _, Keccak};
+
+        let input = (0..1000_u32)
+            .map(|i| (i.wrapping_mul(0x9e37_79b9) >> 24) as u8)
+            .collect::<Vec<_>>();
+        for len in 0..=3 * RATE + 1 {
+            let mut hasher = Keccak::v256();
+            hasher.update(&input[..len]);
+            let mut expected = [0; 32];
+            hasher.finalize(&mut expected);
+            assert_eq!(keccak256(&input[..len]), expected);
+        }
+    }
+}
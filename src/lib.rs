@@ -7,34 +7,164 @@
 //!
 //! Addresses are by default printed with EIP-55 mixed-case checksum encoding.
 //! Addresses checksums may optionally be verified when parsing with
-//! [`Address::from_str_checksum`].
+//! [`Address::from_str_checksum`]. Alternatively, the checksum status of a
+//! parsed address can be inspected with [`Address::parse_with_status`], and
+//! the common wallet policy of accepting all-lowercase and all-uppercase
+//! addresses while rejecting mixed-case addresses with invalid checksums is
+//! implemented by [`Address::from_str_lenient`].
+//!
+//! Some EVM chains, such as RSK, use the EIP-1191 chain-specific checksum
+//! encoding instead. These checksums can be formatted and verified with
+//! [`Address::to_checksum_with_chain`] and
+//! [`Address::from_str_checksum_with_chain`] respectively.
+//!
+//! # Parsing
+//!
+//! The [`Parser`] builder can be used for configuring how strictly addresses
+//! are parsed from strings, for example to require the `0x` prefix, trim
+//! surrounding whitespace or accept short Solidity-style literals.
+//!
+//! Parsing rejects common Unicode confusables, such as fullwidth digits or
+//! Cyrillic letters, and invisible characters with dedicated errors that
+//! name the likely intended hex digit. Alternatively, [`Parser::normalize`]
+//! can be used to opt into normalizing these characters instead.
+//!
+//! For addresses provided as user input, [`lint()`] additionally reports
+//! valid addresses that are likely mistakes, such as the zero address or
+//! addresses without checksums.
+//!
+//! Addresses can also be encoded in and decoded from the IBAN-compatible ICAP
+//! format with [`Address::to_icap`] and [`Address::from_icap`], and paired
+//! with a chain ID as CAIP-10 account IDs with [`caip::ChainAccount`] or,
+//! with the `erc3770` feature, as ERC-3770 chain-specific addresses such as
+//! `eth:0x...`.
 //!
 //! # Features
 //!
 //! This crate supports the following features:
 //! - **_default_ `std`**: Additional integration with Rust standard library
-//! types. Notably, this includes `std::error::Error` implementation on the
-//! [`ParseAddressError`] and conversions from `Vec<u8>`.
+//!   types. Notably, this includes conversions from `Vec<u8>` and the
+//!   [`lookalike::LookalikeIndex`] for address books. Note that
+//!   [`ParseAddressError`] implements `core::error::Error` regardless of this
+//!   feature.
 //! - **_default_ `checksum`**: Include code for encoding and verifying EIP-55
-//! checksummed addresses. This requires Keccak-256 (provided by the [`sha3`]
-//! crate) hashing to be done on the address string.
+//!   checksummed addresses. This requires Keccak-256 hashing to be done on the
+//!   address string. See [Keccak-256 Backends](#keccak-256-backends) for more
+//!   details on how Keccak-256 is provided.
+//! - **_default_ `sha3`**: Use the [`sha3`] crate for Keccak-256 hashing.
+//! - **`tiny-keccak`**: Use the [`tiny_keccak`] crate for Keccak-256 hashing.
+//! - **`create`**: Adds methods for computing contract addresses created by
+//!   the `CREATE` and `CREATE2` opcodes, such as [`Address::create`] and
+//!   [`Address::create2`], as well as the proxy-based CREATE3 pattern with
+//!   [`Address::create3`]. This also adds the [`createx`] module for
+//!   predicting addresses of contracts deployed with the CreateX factory. This
+//!   implies the `checksum` feature for Keccak-256 hashing.
+//! - **`eip712`**: Adds the [`eip712`] module for hashing EIP-712 typed data
+//!   in the JSON format used by `eth_signTypedData_v4` and recovering its
+//!   signer. This requires JSON parsing (provided by the [`serde_json`] crate)
+//!   and implies the `recover`, `serde` and `std` features.
+//! - **`eip681`**: Adds the [`eip681`] module for parsing and formatting
+//!   EIP-681 transaction request URIs, such as `ethereum:` payment requests.
+//!   This implies the `checksum` and `std` features.
+//! - **`erc3770`**: Adds the [`erc3770`] module for parsing and formatting
+//!   ERC-3770 chain-specific addresses, such as `eth:0x...`. Short names are
+//!   resolved with a table of popular chains.
+//! - **`pubkey`**: Adds [`Address::from_public_key`] for deriving addresses
+//!   from secp256k1 public keys. This requires elliptic curve arithmetic
+//!   (provided by the [`k256`] crate) for decompressing public keys, and
+//!   implies the `checksum` feature for Keccak-256 hashing.
+//! - **`qr`**: Adds the [`qr`] module with a small `no_std` QR code encoder
+//!   for addresses and payment request URIs, as well as SVG and plain text
+//!   renderers. This implies the `checksum` feature.
+//! - **`recover`**: Adds [`Address::recover`] for recovering the signer
+//!   address of ECDSA signatures, as well as the [`eip191`] module for
+//!   verifying signed messages. This requires ECDSA public key recovery
+//!   (provided by the [`k256`] crate) and implies the `pubkey` feature.
 //! - **`serde`**: Serialization traits for the [`serde`](::serde) crate. Note
-//! that the implementation is very much geared towards JSON serialiazation with
-//! `serde_json`.
+//!   that the implementation is very much geared towards JSON serialiazation
+//!   with `serde_json`.
+//! - **`siwe`**: Adds the [`siwe`] module for parsing and verifying
+//!   Sign-In with Ethereum (EIP-4361) messages. This implies the `recover` and
+//!   `std` features.
+//! - **`transaction`**: Adds the [`transaction`] module for decoding signed
+//!   raw transactions and recovering their sender. This implies the `recover`
+//!   and `std` features.
 //! - **`macros`**: Adds the [`address`] procedural macro for compile-time
-//! verified address literals.
+//!   verified address literals. Note that the [`const_address`] declarative
+//!   macro can be used for compile-time verified address literals without
+//!   this feature.
+//!
+//! # Keccak-256 Backends
+//!
+//! Keccak-256 hashing, used for checksums as well as for deriving addresses,
+//! is provided by one of the following backends, in order of precedence:
+//! 1. A user-supplied function registered with [`register_keccak256`], when
+//!    compiling with `RUSTFLAGS='--cfg ethaddr_custom_keccak'`.
+//! 2. The [`sha3`] crate, when the `sha3` feature is enabled.
+//! 3. The [`tiny_keccak`] crate, when the `tiny-keccak` feature is enabled.
+//! 4. A built-in implementation otherwise.
+//!
+//! Note that the `checksum` feature does not imply the `sha3` feature, so
+//! disabling default features selects the built-in implementation unless a
+//! backend is enabled explicitly.
+//!
+//! The `ethaddr_custom_keccak` configuration applies to the whole build, and
+//! the registered function is resolved when linking the final binary. In
+//! particular, doctests do not register a function and fail to link with
+//! this configuration, so only library tests (`cargo test --lib`) can be run
+//! with it.
 
 #![cfg_attr(not(any(feature = "std", test)), no_std)]
 
 mod buffer;
+pub mod caip;
 #[cfg(feature = "checksum")]
 mod checksum;
+#[cfg(feature = "create")]
+mod create;
+#[cfg(feature = "create")]
+pub mod createx;
+#[cfg(feature = "recover")]
+pub mod eip191;
+#[cfg(feature = "eip681")]
+pub mod eip681;
+#[cfg(feature = "eip712")]
+pub mod eip712;
+#[cfg(feature = "erc3770")]
+pub mod erc3770;
 mod hex;
+mod icap;
+mod keccak;
+#[cfg(feature = "checksum")]
+pub mod lint;
+pub mod lookalike;
+#[cfg(feature = "pubkey")]
+mod pubkey;
+#[cfg(feature = "qr")]
+pub mod qr;
+#[cfg(feature = "recover")]
+mod recover;
 #[cfg(feature = "serde")]
 mod serde;
+#[cfg(feature = "siwe")]
+pub mod siwe;
+#[cfg(feature = "transaction")]
+pub mod transaction;
 
+pub use crate::buffer::Checksum;
 use crate::buffer::{Alphabet, FormattingBuffer};
-pub use crate::hex::ParseAddressError;
+#[cfg(feature = "checksum")]
+pub use crate::checksum::ChecksumStatus;
+#[cfg(feature = "checksum")]
+pub use crate::hex::{ChecksumPolicy, MismatchedIndices};
+pub use crate::hex::{ParseAddressError, Parser, Prefix};
+pub use crate::icap::{Icap, IcapError};
+#[cfg(feature = "checksum")]
+pub use crate::lint::lint;
+#[cfg(feature = "pubkey")]
+pub use crate::pubkey::PublicKeyError;
+#[cfg(feature = "recover")]
+pub use crate::recover::RecoverError;
 use core::{
     array::{IntoIter, TryFromSliceError},
     fmt::{self, Debug, Display, Formatter, LowerHex, UpperHex},
@@ -77,6 +207,88 @@ use core::{
 #[cfg(feature = "macros")]
 pub use ethaddr_macros::address;
 
+/// Declarative macro to create Ethereum public address values from string
+/// literals that get verified at compile time, without requiring the
+/// `macros` feature. A compiler error will be generated if an invalid address
+/// is specified.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```
+/// # use ethaddr::{const_address, Address};
+/// const ADDRESS: Address = const_address!("0xEeeeeEeeeEeEeeEeEeEeeEEEeeeeEeeeeeeeEEeE");
+/// assert_eq!(ADDRESS, Address([0xee; 20]));
+/// assert_eq!(
+///     const_address!("EeeeeEeeeEeEeeEeEeEeeEEEeeeeEeeeeeeeEEeE"),
+///     Address([0xee; 20]),
+/// );
+/// ```
+///
+/// Invalid addresses fail to compile, even outside of a `const` context:
+///
+/// ```compile_fail
+/// # use ethaddr::const_address;
+/// let _ = const_address!("0xeeee");
+/// ```
+///
+/// Like the [`address`] procedural macro, address checksums are verified:
+///
+/// ```compile_fail
+/// # use ethaddr::const_address;
+/// let _ = const_address!("0xeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeee");
+/// ```
+///
+/// However, this behaviour can be ignored by prefixing the address with a `~`:
+///
+/// ```
+/// # use ethaddr::const_address;
+/// let _ = const_address!(~"0xeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeee");
+/// ```
+#[macro_export]
+macro_rules! const_address {
+    (~ $s:expr $(,)?) => {{
+        const ADDRESS: $crate::Address = $crate::Address::from_hex($s);
+        ADDRESS
+    }};
+    ($s:expr $(,)?) => {{
+        const ADDRESS: $crate::Address = $crate::Address::from_hex_checksum($s);
+        ADDRESS
+    }};
+}
+
+/// Registers a user-supplied Keccak-256 implementation to be used by this
+/// crate when compiling with `RUSTFLAGS='--cfg ethaddr_custom_keccak'`.
+///
+/// The function is called with a list of byte slices, and must return the
+/// Keccak-256 hash of their concatenation. Note that the function must be
+/// registered exactly once in the final binary.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```
+/// fn keccak256(parts: &[&[u8]]) -> [u8; 32] {
+///     // Use some external Keccak-256 implementation...
+/// #   let _ = parts;
+/// #   [0; 32]
+/// }
+///
+/// ethaddr::register_keccak256!(keccak256);
+/// ```
+#[macro_export]
+macro_rules! register_keccak256 {
+    ($path:path $(,)?) => {
+        #[unsafe(no_mangle)]
+        extern "Rust" fn __ethaddr_keccak256(parts: &[&[u8]]) -> [u8; 32] {
+            let f: fn(&[&[u8]]) -> [u8; 32] = $path;
+            f(parts)
+        }
+    };
+}
+
 /// An Ethereum public address.
 #[repr(transparent)]
 #[derive(Copy, Clone, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
@@ -108,6 +320,69 @@ impl Address {
         slice.try_into().unwrap()
     }
 
+    /// Parses an `Address` from a hex string in a `const` context. The hex
+    /// string may optionally be `0x`-prefixed, and its checksum is not
+    /// verified.
+    ///
+    /// # Panics
+    ///
+    /// This method panics if the string is not a valid address. When used
+    /// in a `const` context, this results in a compilation error instead.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// # use ethaddr::Address;
+    /// const ADDRESS: Address = Address::from_hex("0xEeeeeEeeeEeEeeEeEeEeeEEEeeeeEeeeeeeeEEeE");
+    /// assert_eq!(ADDRESS, Address([0xee; 20]));
+    /// ```
+    ///
+    /// Invalid addresses fail to compile:
+    ///
+    /// ```compile_fail
+    /// # use ethaddr::Address;
+    /// const ADDRESS: Address = Address::from_hex("0xgggggggggggggggggggggggggggggggggggggggg");
+    /// ```
+    pub const fn from_hex(s: &str) -> Self {
+        Self(hex::decode_const(s))
+    }
+
+    /// Parses a checksummed `Address` from a hex string in a `const` context.
+    /// The hex string may optionally be `0x`-prefixed.
+    ///
+    /// This uses a built-in `const` Keccak-256 implementation, and so does
+    /// not require the `checksum` feature.
+    ///
+    /// # Panics
+    ///
+    /// This method panics if the string is not a valid address or its
+    /// checksum does not match. When used in a `const` context, this results
+    /// in a compilation error instead.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// # use ethaddr::Address;
+    /// const ADDRESS: Address =
+    ///     Address::from_hex_checksum("0xEeeeeEeeeEeEeeEeEeEeeEEEeeeeEeeeeeeeEEeE");
+    /// assert_eq!(ADDRESS, Address([0xee; 20]));
+    /// ```
+    ///
+    /// Addresses with invalid checksums fail to compile:
+    ///
+    /// ```compile_fail
+    /// # use ethaddr::Address;
+    /// const ADDRESS: Address =
+    ///     Address::from_hex_checksum("0xeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeee");
+    /// ```
+    pub const fn from_hex_checksum(s: &str) -> Self {
+        Self(hex::decode_checksum_const(s))
+    }
+
     /// Creates a reference to an address from a reference to a 20-byte array.
     ///
     /// # Examples
@@ -148,10 +423,132 @@ impl Address {
     #[cfg(feature = "checksum")]
     pub fn from_str_checksum(s: &str) -> Result<Self, ParseAddressError> {
         let bytes = hex::decode(s)?;
-        checksum::verify(&bytes, s).map_err(|_| ParseAddressError::ChecksumMismatch)?;
+        let digits = s.strip_prefix("0x").unwrap_or(s);
+        checksum::verify(&bytes, s).map_err(|expected| {
+            ParseAddressError::checksum_mismatch(expected, digits, s.len() - digits.len())
+        })?;
+        Ok(Self(bytes))
+    }
+
+    /// Parses an `Address` from a string, returning its EIP-55 checksum status
+    /// instead of failing on checksum mismatches.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// # use ethaddr::{Address, ChecksumStatus};
+    /// for (s, status) in [
+    ///     ("0x90F8bf6A479f320ead074411a4B0e7944Ea8c9C1", ChecksumStatus::Valid),
+    ///     ("0x90f8bf6a479f320ead074411a4b0e7944ea8c9c1", ChecksumStatus::AllLowercase),
+    ///     ("0x90F8BF6A479F320EAD074411A4B0E7944EA8C9C1", ChecksumStatus::AllUppercase),
+    ///     ("0x90f8bf6a479f320ead074411a4b0e7944ea8c9C1", ChecksumStatus::Invalid),
+    /// ] {
+    ///     assert_eq!(Address::parse_with_status(s).unwrap().1, status);
+    /// }
+    /// ```
+    #[cfg(feature = "checksum")]
+    pub fn parse_with_status(s: &str) -> Result<(Self, ChecksumStatus), ParseAddressError> {
+        let bytes = hex::decode(s)?;
+        let status = checksum::status(&bytes, s);
+        Ok((Self(bytes), status))
+    }
+
+    /// Parses an `Address` from a string using the lenient wallet checksum
+    /// policy: all-lowercase and all-uppercase addresses are accepted, while
+    /// mixed-case addresses must have a valid EIP-55 checksum.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// # use ethaddr::Address;
+    /// assert!(Address::from_str_lenient("0xEeeeeEeeeEeEeeEeEeEeeEEEeeeeEeeeeeeeEEeE").is_ok());
+    /// assert!(Address::from_str_lenient("0xeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeee").is_ok());
+    /// assert!(Address::from_str_lenient("0xEEEEEEEEEEEEEEEEEEEEEEEEEEEEEEEEEEEEEEEE").is_ok());
+    /// assert!(Address::from_str_lenient("0xeeeeeEeeeEeEeeEeEeEeeEEEeeeeEeeeeeeeEEeE").is_err());
+    /// ```
+    #[cfg(feature = "checksum")]
+    pub fn from_str_lenient(s: &str) -> Result<Self, ParseAddressError> {
+        match Self::parse_with_status(s)? {
+            (address, status) if status.is_acceptable() => Ok(address),
+            _ => Self::from_str_checksum(s),
+        }
+    }
+
+    /// Returns the address formatted with an EIP-55 checksum.
+    ///
+    /// This is equivalent to the [`Display`] implementation, but allows
+    /// access to the checksummed string without allocating.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// # use ethaddr::Address;
+    /// let address = Address([0xee; 20]);
+    /// assert_eq!(
+    ///     address.to_checksum().as_str(),
+    ///     "0xEeeeeEeeeEeEeeEeEeEeeEEEeeeeEeeeeeeeEEeE",
+    /// );
+    /// ```
+    #[cfg(feature = "checksum")]
+    pub fn to_checksum(&self) -> Checksum {
+        Checksum(checksum::fmt(self))
+    }
+
+    /// Parses an `Address` from a string with an EIP-1191 chain-specific
+    /// checksum.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// # use ethaddr::Address;
+    /// let s = "0x5aaEB6053f3e94c9b9a09f33669435E7ef1bEAeD";
+    /// assert!(Address::from_str_checksum_with_chain(s, 30).is_ok());
+    /// assert!(Address::from_str_checksum_with_chain(s, 31).is_err());
+    /// assert!(Address::from_str_checksum(s).is_err());
+    /// ```
+    #[cfg(feature = "checksum")]
+    pub fn from_str_checksum_with_chain(s: &str, chain_id: u64) -> Result<Self, ParseAddressError> {
+        let bytes = hex::decode(s)?;
+        let digits = s.strip_prefix("0x").unwrap_or(s);
+        checksum::verify_with_chain(&bytes, s, Some(chain_id)).map_err(|expected| {
+            ParseAddressError::checksum_mismatch(expected, digits, s.len() - digits.len())
+        })?;
         Ok(Self(bytes))
     }
 
+    /// Returns the address formatted with an EIP-1191 chain-specific
+    /// checksum.
+    ///
+    /// The returned value implements [`Display`], so it can be used directly
+    /// in formatting macros.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// # use ethaddr::Address;
+    /// let address = "0x5aaeb6053f3e94c9b9a09f33669435e7ef1beaed"
+    ///     .parse::<Address>()
+    ///     .unwrap();
+    /// assert_eq!(
+    ///     address.to_checksum_with_chain(30).to_string(),
+    ///     "0x5aaEB6053f3e94c9b9a09f33669435E7ef1bEAeD",
+    /// );
+    /// ```
+    #[cfg(feature = "checksum")]
+    pub fn to_checksum_with_chain(&self, chain_id: u64) -> Checksum {
+        Checksum(checksum::fmt_with_chain(self, Some(chain_id)))
+    }
+
     /// Default formatting method for an address.
     fn fmt(&self) -> FormattingBuffer {
         #[cfg(feature = "checksum")]
@@ -201,6 +598,33 @@ impl UpperHex for Address {
     }
 }
 
+impl Parser {
+    /// Parses an `Address` from a string using the configured options.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// # use ethaddr::{Address, Parser, Prefix};
+    /// let parser = Parser::new()
+    ///     .prefix(Prefix::Required)
+    ///     .uppercase_prefix(true)
+    ///     .trim(true)
+    ///     .short(true);
+    /// assert_eq!(
+    ///     parser.parse(" \"0X1\" ").unwrap(),
+    ///     Address([
+    ///         0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1
+    ///     ]),
+    /// );
+    /// assert!(parser.parse("1").is_err());
+    /// ```
+    pub fn parse(&self, s: &str) -> Result<Address, ParseAddressError> {
+        self.decode(s).map(Address)
+    }
+}
+
 impl AsRef<[u8; 20]> for Address {
     fn as_ref(&self) -> &[u8; 20] {
         &self.0
@@ -337,10 +761,47 @@ impl TryFrom<Vec<u8>> for Address {
     }
 }
 
+/// Helpers shared by the unit tests of multiple modules.
+#[cfg(test)]
+mod test_support {
+    /// Parses a hex string, with an optional `0x` prefix, into bytes.
+    #[cfg(any(feature = "eip712", feature = "transaction"))]
+    pub fn bytes(s: &str) -> Vec<u8> {
+        let s = s.strip_prefix("0x").unwrap_or(s);
+        (0..s.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&s[i..i + 2], 16).unwrap())
+            .collect()
+    }
+
+    /// Parses a hex string, with an optional `0x` prefix, into a 32-byte word.
+    #[cfg(any(feature = "create", feature = "recover"))]
+    pub fn bytes32(s: &str) -> [u8; 32] {
+        let s = s.strip_prefix("0x").unwrap_or(s);
+        let mut bytes = [0; 32];
+        for (i, byte) in bytes.iter_mut().enumerate() {
+            *byte = u8::from_str_radix(&s[i * 2..][..2], 16).unwrap();
+        }
+        bytes
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[cfg(all(ethaddr_custom_keccak, feature = "checksum"))]
+    crate::register_keccak256!(custom_keccak256);
+
+    #[cfg(all(ethaddr_custom_keccak, feature = "checksum"))]
+    fn custom_keccak256(parts: &[&[u8]]) -> [u8; 32] {
+        let mut hasher = keccak::Keccak256::new();
+        for part in parts {
+            hasher.update(part);
+        }
+        hasher.finalize()
+    }
+
     #[test]
     fn checksum_address() {
         for s in [
@@ -375,6 +836,380 @@ mod tests {
         assert!(Address::from_str_checksum("eeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeee").is_err());
     }
 
+    #[cfg(feature = "checksum")]
+    #[test]
+    fn eip1191_checksum() {
+        for (chain_id, addresses) in [
+            (
+                30,
+                &[
+                    "0x27b1FdB04752BBc536007A920D24ACB045561c26",
+                    "0x3599689E6292B81B2D85451025146515070129Bb",
+                    "0x42712D45473476B98452f434E72461577d686318",
+                    "0x52908400098527886E0F7030069857D2E4169ee7",
+                    "0x5aaEB6053f3e94c9b9a09f33669435E7ef1bEAeD",
+                    "0x6549F4939460DE12611948B3F82B88C3C8975323",
+                    "0x88021160c5C792225E4E5452585947470010289d",
+                    "0xD1220A0Cf47c7B9BE7a2e6ba89F429762E7B9adB",
+                    "0xDBF03B407c01E7CD3cBea99509D93F8Dddc8C6FB",
+                    "0xFb6916095cA1Df60bb79ce92cE3EA74c37c5d359",
+                ][..],
+            ),
+            (
+                31,
+                &[
+                    "0x27B1FdB04752BbC536007a920D24acB045561C26",
+                    "0x3599689e6292b81b2D85451025146515070129Bb",
+                    "0x42712D45473476B98452F434E72461577D686318",
+                    "0x5aAeb6053F3e94c9b9A09F33669435E7EF1BEaEd",
+                    "0x88021160c5C792225E4E5452585947470010289d",
+                    "0xFb6916095CA1dF60bb79CE92ce3Ea74C37c5D359",
+                ][..],
+            ),
+        ] {
+            for &s in addresses {
+                let address = Address::from_str_checksum_with_chain(s, chain_id).unwrap();
+                assert_eq!(address.to_checksum_with_chain(chain_id).to_string(), s);
+            }
+        }
+    }
+
+    #[cfg(feature = "checksum")]
+    #[test]
+    fn checksum_status() {
+        // Addresses without any letters always have valid checksums.
+        let (_, status) =
+            Address::parse_with_status("0x1111111111111111111111111111111111111111").unwrap();
+        assert_eq!(status, ChecksumStatus::Valid);
+
+        for (s, status) in [
+            (
+                "eeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeee",
+                ChecksumStatus::AllLowercase,
+            ),
+            (
+                "EEEEEEEEEEEEEEEEEEEEEEEEEEEEEEEEEEEEEEEE",
+                ChecksumStatus::AllUppercase,
+            ),
+            (
+                "0xEeeeeEeeeEeEeeEeEeEeeEEEeeeeEeeeeeeeEEEe",
+                ChecksumStatus::Invalid,
+            ),
+        ] {
+            assert_eq!(
+                Address::parse_with_status(s).unwrap(),
+                (Address([0xee; 20]), status)
+            );
+        }
+        assert_eq!(
+            Address::from_str_lenient("0xEeeeeEeeeEeEeeEeEeEeeEEEeeeeEeeeeeeeEEEe"),
+            Err(ParseAddressError::ChecksumMismatch {
+                expected: Address([0xee; 20]).to_checksum(),
+                index: 2,
+                mismatches: MismatchedIndices::new(2, [40, 41]),
+            }),
+        );
+    }
+
+    #[test]
+    fn parser_options() {
+        let address = Address([0xee; 20]);
+        let digits = "eeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeee";
+
+        let parser = Parser::new().prefix(Prefix::Required);
+        assert_eq!(parser.parse(&format!("0x{digits}")), Ok(address));
+        assert_eq!(parser.parse(digits), Err(ParseAddressError::MissingPrefix));
+
+        let parser = Parser::new().prefix(Prefix::Forbidden);
+        assert_eq!(parser.parse(digits), Ok(address));
+        assert_eq!(
+            parser.parse(&format!("0x{digits}")),
+            Err(ParseAddressError::UnexpectedPrefix),
+        );
+
+        let parser = Parser::new().uppercase_prefix(true);
+        assert_eq!(parser.parse(&format!("0X{digits}")), Ok(address));
+        assert!(Parser::new().parse(&format!("0X{digits}")).is_err());
+
+        let parser = Parser::new().trim(true);
+        assert_eq!(parser.parse(&format!(" \t'0x{digits}'\n")), Ok(address));
+        assert_eq!(
+            parser.parse(&format!(" \"0x{digits}g\"")),
+            Err(ParseAddressError::InvalidLength {
+                len: 41,
+                prefixed: true
+            }),
+        );
+        assert_eq!(
+            parser.parse(&format!(" \"0x{}g\"", &digits[1..])),
+            Err(ParseAddressError::InvalidHexCharacter { c: 'g', index: 43 }),
+        );
+
+        let parser = Parser::new().short(true);
+        assert_eq!(
+            parser.parse("0x0f"),
+            Ok(Address([
+                0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0xf
+            ])),
+        );
+        assert_eq!(
+            parser.parse("0x1g"),
+            Err(ParseAddressError::InvalidHexCharacter { c: 'g', index: 3 }),
+        );
+        assert_eq!(
+            parser.parse("0x"),
+            Err(ParseAddressError::InvalidLength {
+                len: 0,
+                prefixed: true
+            }),
+        );
+
+        let parser = Parser::new().padded(true);
+        assert_eq!(
+            parser.parse(&format!("0x000000000000000000000000{digits}")),
+            Ok(address),
+        );
+        assert_eq!(
+            parser.parse(&format!("0x000000000000000000000001{digits}")),
+            Err(ParseAddressError::InvalidPadding),
+        );
+        assert_eq!(
+            Parser::new().parse(&format!("0x000000000000000000000000{digits}")),
+            Err(ParseAddressError::InvalidLength {
+                len: 64,
+                prefixed: true
+            }),
+        );
+    }
+
+    #[test]
+    fn parser_confusables() {
+        // Fullwidth `e` at the last position.
+        let fullwidth = "0xeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeee\u{ff45}";
+        // Cyrillic `e` at the first position.
+        let cyrillic = "0x\u{435}eeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeee";
+        // Letter `O` instead of a `0` prefix.
+        let letter = "Oxeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeee";
+        // Zero-width space in the middle of the address.
+        let invisible = "0xeeeeeeeeeeeeeeeeeeee\u{200b}eeeeeeeeeeeeeeeeeeee";
+
+        for (s, err) in [
+            (
+                fullwidth,
+                ParseAddressError::ConfusableCharacter {
+                    c: '\u{ff45}',
+                    index: 41,
+                    intended: 'e',
+                },
+            ),
+            (
+                cyrillic,
+                ParseAddressError::ConfusableCharacter {
+                    c: '\u{435}',
+                    index: 2,
+                    intended: 'e',
+                },
+            ),
+            (
+                letter,
+                ParseAddressError::ConfusableCharacter {
+                    c: 'O',
+                    index: 0,
+                    intended: '0',
+                },
+            ),
+            (
+                invisible,
+                ParseAddressError::InvisibleCharacter {
+                    c: '\u{200b}',
+                    index: 22,
+                },
+            ),
+            (
+                // Only the first invalid character is reported.
+                "0xgeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeel",
+                ParseAddressError::InvalidHexCharacter { c: 'g', index: 2 },
+            ),
+        ] {
+            assert_eq!(s.parse::<Address>(), Err(err));
+        }
+
+        // The uppercase `0X` prefix is only skipped when the parser accepts it.
+        let uppercase = "0Xeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeee\u{ff45}";
+        assert_eq!(
+            uppercase.parse::<Address>(),
+            Err(ParseAddressError::InvalidLength {
+                len: 44,
+                prefixed: false
+            }),
+        );
+        assert_eq!(
+            Parser::new().uppercase_prefix(true).parse(uppercase),
+            Err(ParseAddressError::ConfusableCharacter {
+                c: '\u{ff45}',
+                index: 41,
+                intended: 'e',
+            }),
+        );
+
+        let parser = Parser::new().normalize(true);
+        for s in [fullwidth, cyrillic, letter, invisible] {
+            assert_eq!(parser.parse(s), Ok(Address([0xee; 20])));
+        }
+        assert_eq!(
+            parser.parse("0xeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeee\u{4e00}"),
+            Err(ParseAddressError::InvalidHexCharacter {
+                c: '\u{4e00}',
+                index: 41,
+            }),
+        );
+
+        // Indices refer to the input string, even after removing invisible
+        // characters and trimming.
+        assert_eq!(
+            parser
+                .trim(true)
+                .parse(" 0x\u{200b}geeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeee"),
+            Err(ParseAddressError::InvalidHexCharacter { c: 'g', index: 6 }),
+        );
+        assert_eq!(
+            parser.parse(&format!("0x{}{}", "\u{200b}".repeat(80), "e".repeat(40))),
+            Err(ParseAddressError::InvalidLength {
+                len: 280,
+                prefixed: true
+            }),
+        );
+    }
+
+    #[cfg(feature = "checksum")]
+    #[test]
+    fn parser_normalized_checksum_mismatch_indices() {
+        let parser = Parser::new()
+            .normalize(true)
+            .checksum(ChecksumPolicy::Required);
+        for (s, index, indices) in [
+            (
+                "0x\u{200b}EeeeeEeeeEeEeeEeEeEeeEEEeeeeEeeeeeeeEEEe",
+                5,
+                [43, 44],
+            ),
+            (
+                "0x\u{ff25}eeeeEeeeEeEeeEeEeEeeEEEeeeeEeeeeeeeEEEe",
+                2,
+                [42, 43],
+            ),
+        ] {
+            let err = parser.parse(s).unwrap_err();
+            assert!(matches!(
+                err,
+                ParseAddressError::ChecksumMismatch { index: i, .. } if i == index
+            ));
+            assert_eq!(err.mismatched_indices().collect::<Vec<_>>(), indices);
+        }
+    }
+
+    #[cfg(feature = "checksum")]
+    #[test]
+    fn parser_checksum_policy() {
+        let checksummed = "0xEeeeeEeeeEeEeeEeEeEeeEEEeeeeEeeeeeeeEEeE";
+        let invalid = "0xEeeeeEeeeEeEeeEeEeEeeEEEeeeeEeeeeeeeEEEe";
+        let lowercase = "0xeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeee";
+
+        let parser = Parser::new().checksum(ChecksumPolicy::Required);
+        assert!(parser.parse(checksummed).is_ok());
+        assert!(parser.parse(invalid).is_err());
+        assert!(parser.parse(lowercase).is_err());
+
+        let parser = Parser::new().checksum(ChecksumPolicy::MixedCase);
+        assert!(parser.parse(checksummed).is_ok());
+        assert!(parser.parse(invalid).is_err());
+        assert!(parser.parse(lowercase).is_ok());
+
+        let parser = parser.padded(true);
+        assert!(parser
+            .parse("0x000000000000000000000000EeeeeEeeeEeEeeEeEeEeeEEEeeeeEeeeeeeeEEeE")
+            .is_ok());
+    }
+
+    #[cfg(feature = "checksum")]
+    #[test]
+    fn parser_checksum_mismatch_indices() {
+        let parser = Parser::new()
+            .checksum(ChecksumPolicy::Required)
+            .trim(true)
+            .short(true)
+            .padded(true);
+        for (s, indices) in [
+            ("EeeeeEeeeEeEeeEeEeEeeEEEeeeeEeeeeeeeEEEe", &[38, 39][..]),
+            ("0xEeeeeEeeeEeEeeEeEeEeeEEEeeeeEeeeeeeeEEEe", &[40, 41]),
+            (
+                " \"0xEeeeeEeeeEeEeeEeEeEeeEEEeeeeEeeeeeeeEEEe\" ",
+                &[42, 43],
+            ),
+            (
+                "0x000000000000000000000000EeeeeEeeeEeEeeEeEeEeeEEEeeeeEeeeeeeeEEEe",
+                &[64, 65],
+            ),
+            ("0xeeee", &[2, 3, 4]),
+            ("  0xEEEE", &[7]),
+        ] {
+            let err = parser.parse(s).unwrap_err();
+            assert!(matches!(err, ParseAddressError::ChecksumMismatch { .. }));
+            assert_eq!(err.mismatched_indices().collect::<Vec<_>>(), indices);
+        }
+
+        let err =
+            Address::from_str_checksum("0xEeeeeEeeeEeEeeEeEeEeeEEEeeeeEeeeeeeeEEEe").unwrap_err();
+        assert_eq!(err.mismatched_indices().collect::<Vec<_>>(), [40, 41]);
+        assert_eq!(
+            ParseAddressError::MissingPrefix.mismatched_indices().next(),
+            None,
+        );
+    }
+
+    #[test]
+    fn const_parsing() {
+        const ADDRESS: Address = Address::from_hex("0x90F8bf6A479f320ead074411a4B0e7944Ea8c9C1");
+        assert_eq!(
+            ADDRESS,
+            "0x90F8bf6A479f320ead074411a4B0e7944Ea8c9C1"
+                .parse::<Address>()
+                .unwrap(),
+        );
+        assert_eq!(
+            const_address!(~"90f8bf6a479f320ead074411a4b0e7944ea8c9c1"),
+            ADDRESS,
+        );
+        assert_eq!(
+            const_address!("90F8bf6A479f320ead074411a4B0e7944Ea8c9C1"),
+            ADDRESS,
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn const_parsing_panics_at_runtime() {
+        let s = std::hint::black_box("0x90F8bf6A479f320ead074411a4B0e7944Ea8c9Cg");
+        Address::from_hex(s);
+    }
+
+    #[test]
+    #[should_panic]
+    fn const_checksum_panics_at_runtime() {
+        let s = std::hint::black_box("0x90F8bf6A479f320ead074411a4B0e7944Ea8c9c1");
+        Address::from_hex_checksum(s);
+    }
+
+    #[cfg(feature = "checksum")]
+    #[test]
+    fn const_checksum_matches_runtime_checksum() {
+        for i in 0..=255 {
+            let address = Address([i; 20]);
+            let checksum = address.to_checksum();
+            assert_eq!(Address::from_hex_checksum(checksum.as_str()), address);
+        }
+    }
+
     #[test]
     fn hex_formatting() {
         let address = Address([0xee; 20]);
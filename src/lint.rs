@@ -0,0 +1,294 @@
+//! Diagnostics for addresses provided as user input.
+//!
+//! Parsing an address only reports errors for input that does not represent
+//! an address. However, there are valid addresses that are still likely to
+//! be mistakes, such as the zero address or addresses without checksums.
+//! [`lint`] parses an address string and additionally reports these as
+//! severity-tagged findings.
+
+use crate::{hex, Address, ChecksumStatus, ParseAddressError};
+use core::fmt::{self, Display, Formatter};
+
+/// Parses an address string provided as user input and reports diagnostics.
+///
+/// Surrounding whitespace and quotes are trimmed, and mixed-case addresses
+/// are required to have a valid checksum.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```
+/// # use ethaddr::{lint::{Finding, Severity}, Address};
+/// let diagnostics = ethaddr::lint(" 0x0000000000000000000000000000000000000000");
+/// assert_eq!(diagnostics.address, Ok(Address([0; 20])));
+/// assert!(diagnostics.has(Finding::ZeroAddress));
+/// assert!(diagnostics.has(Finding::Trimmed));
+/// assert_eq!(diagnostics.max_severity(), Some(Severity::Error));
+/// ```
+pub fn lint(s: &str) -> Diagnostics {
+    let mut findings = Findings::default();
+
+    let trimmed = s.trim_matches(hex::is_trimmed);
+    if trimmed.len() != s.len() {
+        findings.insert(Finding::Trimmed);
+    }
+
+    let address = match Address::parse_with_status(trimmed) {
+        Ok((address, status)) => match status {
+            ChecksumStatus::Valid => Ok(address),
+            ChecksumStatus::AllLowercase | ChecksumStatus::AllUppercase => {
+                findings.insert(Finding::NoChecksum);
+                Ok(address)
+            }
+            // Report the checksum mismatch error with the expected checksum.
+            ChecksumStatus::Invalid => Address::from_str_checksum(trimmed),
+        },
+        Err(err) => Err(err),
+    };
+
+    if let Ok(address) = &address {
+        if *address == Address([0; 20]) {
+            findings.insert(Finding::ZeroAddress);
+        } else if is_precompile(address) {
+            findings.insert(Finding::Precompile);
+        } else if SYSTEM_CONTRACTS.contains(&address.0) {
+            findings.insert(Finding::SystemContract);
+        } else if is_low_entropy(address) {
+            findings.insert(Finding::LowEntropy);
+        }
+    }
+
+    Diagnostics { address, findings }
+}
+
+/// The result of linting an address string.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Diagnostics {
+    /// The parsed address.
+    pub address: Result<Address, ParseAddressError>,
+    findings: Findings,
+}
+
+impl Diagnostics {
+    /// Returns `true` if the diagnostics include the specified finding.
+    pub fn has(&self, finding: Finding) -> bool {
+        self.findings.contains(finding)
+    }
+
+    /// Returns an iterator over the findings.
+    pub fn findings(&self) -> impl Iterator<Item = Finding> + '_ {
+        Finding::ALL
+            .into_iter()
+            .filter(|finding| self.findings.contains(*finding))
+    }
+
+    /// Returns the highest severity of the diagnostics. Parsing errors are
+    /// always reported with [`Severity::Error`].
+    pub fn max_severity(&self) -> Option<Severity> {
+        if self.address.is_err() {
+            return Some(Severity::Error);
+        }
+        self.findings().map(|finding| finding.severity()).max()
+    }
+}
+
+/// The severity of a finding.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum Severity {
+    /// Informational, the input is most likely fine.
+    Info,
+    /// The address is valid but should be double-checked.
+    Warning,
+    /// The address is almost certainly a mistake.
+    Error,
+}
+
+/// A finding reported by [`lint`].
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Finding {
+    /// The address is the zero address.
+    ZeroAddress,
+    /// The address is a precompiled contract.
+    Precompile,
+    /// The address is a well-known system contract.
+    SystemContract,
+    /// The address is all lowercase or all uppercase, and therefore carries
+    /// no checksum protection against typos.
+    NoChecksum,
+    /// The address is mostly made up of a single repeated hex digit.
+    LowEntropy,
+    /// The input had surrounding whitespace or quotes that were trimmed.
+    Trimmed,
+}
+
+impl Finding {
+    /// All findings, in the order they are reported.
+    const ALL: [Self; 6] = [
+        Self::ZeroAddress,
+        Self::Precompile,
+        Self::SystemContract,
+        Self::NoChecksum,
+        Self::LowEntropy,
+        Self::Trimmed,
+    ];
+
+    /// Returns the severity of the finding.
+    pub fn severity(&self) -> Severity {
+        match self {
+            Self::ZeroAddress | Self::Precompile => Severity::Error,
+            Self::SystemContract | Self::NoChecksum | Self::LowEntropy => Severity::Warning,
+            Self::Trimmed => Severity::Info,
+        }
+    }
+}
+
+impl Display for Finding {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Self::ZeroAddress => write!(f, "address is the zero address"),
+            Self::Precompile => write!(f, "address is a precompiled contract"),
+            Self::SystemContract => write!(f, "address is a system contract"),
+            Self::NoChecksum => write!(f, "address does not have a checksum"),
+            Self::LowEntropy => write!(f, "address has suspiciously low entropy"),
+            Self::Trimmed => write!(f, "input was trimmed"),
+        }
+    }
+}
+
+/// A set of findings.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+struct Findings(u8);
+
+impl Findings {
+    fn insert(&mut self, finding: Finding) {
+        self.0 |= 1 << finding as u8;
+    }
+
+    fn contains(&self, finding: Finding) -> bool {
+        self.0 & (1 << finding as u8) != 0
+    }
+}
+
+/// Well-known system contracts.
+const SYSTEM_CONTRACTS: [[u8; 20]; 6] = [
+    // EIP-4788 beacon block root contract.
+    [
+        0x00, 0x0f, 0x3d, 0xf6, 0xd7, 0x32, 0x80, 0x7e, 0xf1, 0x31, 0x9f, 0xb7, 0xb8, 0xbb, 0x85,
+        0x22, 0xd0, 0xbe, 0xac, 0x02,
+    ],
+    // EIP-2935 historical block hashes contract.
+    [
+        0x00, 0x00, 0xf9, 0x08, 0x27, 0xf1, 0xc5, 0x3a, 0x10, 0xcb, 0x7a, 0x02, 0x33, 0x5b, 0x17,
+        0x53, 0x20, 0x00, 0x29, 0x35,
+    ],
+    // EIP-7002 withdrawal request contract.
+    [
+        0x00, 0x00, 0x09, 0x61, 0xef, 0x48, 0x0e, 0xb5, 0x5e, 0x80, 0xd1, 0x9a, 0xd8, 0x35, 0x79,
+        0xa6, 0x4c, 0x00, 0x70, 0x02,
+    ],
+    // EIP-7251 consolidation request contract.
+    [
+        0x00, 0x00, 0xbb, 0xdd, 0xc7, 0xce, 0x48, 0x86, 0x42, 0xfb, 0x57, 0x9f, 0x8b, 0x00, 0xf3,
+        0xa5, 0x90, 0x00, 0x72, 0x51,
+    ],
+    // Beacon chain deposit contract.
+    [
+        0x00, 0x00, 0x00, 0x00, 0x21, 0x9a, 0xb5, 0x40, 0x35, 0x6c, 0xbb, 0x83, 0x9c, 0xbe, 0x05,
+        0x30, 0x3d, 0x77, 0x05, 0xfa,
+    ],
+    // System address used for system calls.
+    [
+        0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
+        0xff, 0xff, 0xff, 0xff, 0xfe,
+    ],
+];
+
+/// Returns `true` if the address is a precompiled contract: `0x01` through
+/// `0x11`, as well as the `P256VERIFY` precompile at `0x100`.
+fn is_precompile(address: &Address) -> bool {
+    match address.0 {
+        [0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, n] => (0x01..=0x11).contains(&n),
+        [0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1, 0] => true,
+        _ => false,
+    }
+}
+
+/// Returns `true` if most of the address's hex digits are the same.
+fn is_low_entropy(address: &Address) -> bool {
+    let mut counts = [0_u8; 16];
+    for byte in address {
+        counts[(byte >> 4) as usize] += 1;
+        counts[(byte & 0xf) as usize] += 1;
+    }
+    counts.into_iter().max().unwrap_or_default() >= 24
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn system_contract_checksums() {
+        for s in [
+            "0x000F3df6D732807Ef1319fB7B8bB8522d0Beac02",
+            "0x0000F90827F1C53a10cb7A02335B175320002935",
+            "0x00000961Ef480Eb55e80D19ad83579A64c007002",
+            "0x0000BBdDc7CE488642fb579F8B00f3a590007251",
+            "0x00000000219ab540356cBB839Cbe05303d7705Fa",
+        ] {
+            let diagnostics = lint(s);
+            assert!(diagnostics.address.is_ok());
+            assert_eq!(
+                diagnostics.findings().collect::<Vec<_>>(),
+                [Finding::SystemContract],
+            );
+        }
+    }
+
+    #[test]
+    fn findings() {
+        for (s, findings) in [
+            ("0x90F8bf6A479f320ead074411a4B0e7944Ea8c9C1", &[][..]),
+            (
+                "0x90f8bf6a479f320ead074411a4b0e7944ea8c9c1",
+                &[Finding::NoChecksum],
+            ),
+            (
+                "0x0000000000000000000000000000000000000001",
+                &[Finding::Precompile],
+            ),
+            (
+                "0x0000000000000000000000000000000000000100",
+                &[Finding::Precompile],
+            ),
+            (
+                "0x0000000000000000000000000000000000000012",
+                &[Finding::LowEntropy],
+            ),
+            (
+                "\"0xeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeee\"\n",
+                &[Finding::NoChecksum, Finding::LowEntropy, Finding::Trimmed],
+            ),
+            ("0x00000000000000ADc04C56Bf30aC9d3c0aAF14dC", &[]),
+        ] {
+            let diagnostics = lint(s);
+            assert!(diagnostics.address.is_ok());
+            assert_eq!(diagnostics.findings().collect::<Vec<_>>(), findings);
+        }
+    }
+
+    #[test]
+    fn parse_errors() {
+        let diagnostics = lint("0x90F8bf6A479f320ead074411a4B0e7944Ea8c9c1");
+        assert!(matches!(
+            diagnostics.address,
+            Err(ParseAddressError::ChecksumMismatch { .. }),
+        ));
+        assert_eq!(diagnostics.max_severity(), Some(Severity::Error));
+
+        let diagnostics = lint("0x1234");
+        assert!(diagnostics.address.is_err());
+        assert_eq!(diagnostics.findings().count(), 0);
+    }
+}
@@ -0,0 +1,254 @@
+//! Detection of lookalike addresses used in address poisoning scams.
+//!
+//! Address poisoning scams rely on users only checking the first and last few
+//! characters of an address. Attackers generate vanity addresses that share
+//! these characters with one of the victim's real counterparties, and plant
+//! them in the victim's transaction history hoping that they get copied by
+//! mistake.
+
+use crate::{
+    buffer::{self, Alphabet},
+    Address,
+};
+#[cfg(feature = "std")]
+use std::{collections::BTreeSet, ops::Bound};
+
+/// A score describing how similar two addresses look.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub struct LookalikeScore {
+    /// The number of matching leading hex digits.
+    pub prefix: usize,
+    /// The number of matching trailing hex digits.
+    pub suffix: usize,
+}
+
+impl LookalikeScore {
+    /// Returns the total number of matching leading and trailing hex digits.
+    pub fn total(&self) -> usize {
+        self.prefix + self.suffix
+    }
+
+    /// Returns `true` if the score corresponds to identical addresses.
+    pub fn is_identical(&self) -> bool {
+        self.prefix == 40
+    }
+}
+
+impl Address {
+    /// Computes how similar two addresses look, based on the number of
+    /// matching leading and trailing hex digits.
+    ///
+    /// Digits are compared without regard to case: the mixed-case checksum of
+    /// a lookalike address will almost certainly differ, but this is not
+    /// something that users reliably notice.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// # use ethaddr::{Address, lookalike::LookalikeScore};
+    /// let real = "0xd8dA6BF26964aF9D7eEd9e03E53415D37aA96045"
+    ///     .parse::<Address>()
+    ///     .unwrap();
+    /// let poisoned = "0xd8da00000000000000000000000000000000d045"
+    ///     .parse::<Address>()
+    ///     .unwrap();
+    /// assert_eq!(
+    ///     real.lookalike_score(&poisoned),
+    ///     LookalikeScore { prefix: 4, suffix: 3 },
+    /// );
+    /// ```
+    pub fn lookalike_score(&self, other: &Address) -> LookalikeScore {
+        let (a, b) = (
+            buffer::fmt(self, Alphabet::Lower),
+            buffer::fmt(other, Alphabet::Lower),
+        );
+        let digits = || {
+            a.as_bytes_str()
+                .bytes()
+                .zip(b.as_bytes_str().bytes())
+                .map(|(a, b)| a == b)
+        };
+
+        LookalikeScore {
+            prefix: digits().take_while(|eq| *eq).count(),
+            suffix: digits().rev().take_while(|eq| *eq).count(),
+        }
+    }
+}
+
+/// An index of known addresses, such as an address book, for finding entries
+/// that look confusingly similar to a new address.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```
+/// # use ethaddr::{Address, lookalike::LookalikeIndex};
+/// let mut index = LookalikeIndex::default();
+/// index.insert(
+///     "0xd8dA6BF26964aF9D7eEd9e03E53415D37aA96045"
+///         .parse()
+///         .unwrap(),
+/// );
+///
+/// let poisoned = "0xd8da6b000000000000000000000000000aa96045"
+///     .parse::<Address>()
+///     .unwrap();
+/// assert_eq!(index.lookalikes(&poisoned).count(), 1);
+/// ```
+#[cfg(feature = "std")]
+#[derive(Clone, Debug)]
+pub struct LookalikeIndex {
+    entries: BTreeSet<Address>,
+    min_prefix: usize,
+    min_suffix: usize,
+}
+
+#[cfg(feature = "std")]
+impl LookalikeIndex {
+    /// Creates a new empty index, where addresses are considered lookalikes
+    /// if they share at least `min_prefix` leading and `min_suffix` trailing
+    /// hex digits.
+    ///
+    /// # Panics
+    ///
+    /// This method panics if either length is more than 40 hex digits.
+    pub fn new(min_prefix: usize, min_suffix: usize) -> Self {
+        assert!(
+            min_prefix <= 40 && min_suffix <= 40,
+            "invalid lookalike thresholds",
+        );
+        Self {
+            entries: BTreeSet::new(),
+            min_prefix,
+            min_suffix,
+        }
+    }
+
+    /// Adds an address to the index. Returns `false` if the address was
+    /// already present.
+    pub fn insert(&mut self, address: Address) -> bool {
+        self.entries.insert(address)
+    }
+
+    /// Removes an address from the index. Returns `false` if the address was
+    /// not present.
+    pub fn remove(&mut self, address: &Address) -> bool {
+        self.entries.remove(address)
+    }
+
+    /// Returns `true` if the index contains the exact address.
+    pub fn contains(&self, address: &Address) -> bool {
+        self.entries.contains(address)
+    }
+
+    /// Returns the number of addresses in the index.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns `true` if the index is empty.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Returns the entries of the index that look confusingly similar to, but
+    /// are not identical to, the specified address along with their scores.
+    pub fn lookalikes<'a>(
+        &'a self,
+        address: &'a Address,
+    ) -> impl Iterator<Item = (&'a Address, LookalikeScore)> + 'a {
+        // Addresses are ordered by their bytes, so all entries sharing the
+        // leading hex digits of the address form a contiguous range.
+        let (mut lower, mut upper) = (*address, *address);
+        for i in self.min_prefix..40 {
+            let mask = if i % 2 == 0 { 0xf0 } else { 0x0f };
+            lower[i / 2] &= !mask;
+            upper[i / 2] |= mask;
+        }
+
+        self.entries
+            .range((Bound::Included(lower), Bound::Included(upper)))
+            .filter(move |entry| *entry != address)
+            .map(move |entry| (entry, address.lookalike_score(entry)))
+            .filter(move |(_, score)| score.suffix >= self.min_suffix)
+    }
+}
+
+#[cfg(feature = "std")]
+impl Default for LookalikeIndex {
+    /// Creates an index matching on the first and last 4 hex digits, which is
+    /// commonly what wallets display for shortened addresses.
+    fn default() -> Self {
+        Self::new(4, 4)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lookalike_scores() {
+        let address = Address([0xee; 20]);
+        assert_eq!(
+            address.lookalike_score(&address),
+            LookalikeScore {
+                prefix: 40,
+                suffix: 40,
+            },
+        );
+        assert!(address.lookalike_score(&address).is_identical());
+
+        let mut other = address;
+        other[0] = 0xe0;
+        other[19] = 0x0e;
+        assert_eq!(
+            address.lookalike_score(&other),
+            LookalikeScore {
+                prefix: 1,
+                suffix: 1,
+            },
+        );
+        assert_eq!(address.lookalike_score(&Address([0; 20])).total(), 0);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn index_lookalikes() {
+        let mut index = LookalikeIndex::new(2, 3);
+        let entries = [
+            Address([0x11; 20]),
+            Address([0x12; 20]),
+            Address([0x22; 20]),
+        ];
+        for entry in entries {
+            assert!(index.insert(entry));
+        }
+        assert!(!index.insert(entries[0]));
+        assert_eq!(index.len(), 3);
+
+        let mut address = [0x11; 20];
+        address[1] = 0x00;
+        let address = Address(address);
+        assert_eq!(
+            index.lookalikes(&address).collect::<Vec<_>>(),
+            [(
+                &entries[0],
+                LookalikeScore {
+                    prefix: 2,
+                    suffix: 36,
+                }
+            )],
+        );
+
+        // Identical entries are not lookalikes.
+        assert_eq!(index.lookalikes(&entries[0]).count(), 0);
+
+        assert!(index.remove(&entries[0]));
+        assert_eq!(index.lookalikes(&address).count(), 0);
+    }
+}
@@ -0,0 +1,115 @@
+//! Derivation of Ethereum public addresses from secp256k1 public keys.
+
+use crate::{checksum, Address};
+use core::fmt::{self, Display, Formatter};
+use k256::{elliptic_curve::sec1::ToEncodedPoint as _, PublicKey};
+
+impl Address {
+    /// Derives an address from a secp256k1 public key.
+    ///
+    /// The public key can be specified in any of the following encodings:
+    /// - 33-byte SEC1 compressed point (`0x02` or `0x03` prefixed)
+    /// - 65-byte SEC1 uncompressed point (`0x04` prefixed)
+    /// - 64-byte raw uncompressed point (the `x` and `y` coordinates without
+    ///   any prefix)
+    ///
+    /// The address is the last 20 bytes of the Keccak-256 hash of the 64-byte
+    /// uncompressed point.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// # use ethaddr::Address;
+    /// let compressed = [
+    ///     0x02, 0x79, 0xbe, 0x66, 0x7e, 0xf9, 0xdc, 0xbb, 0xac, 0x55, 0xa0,
+    ///     0x62, 0x95, 0xce, 0x87, 0x0b, 0x07, 0x02, 0x9b, 0xfc, 0xdb, 0x2d,
+    ///     0xce, 0x28, 0xd9, 0x59, 0xf2, 0x81, 0x5b, 0x16, 0xf8, 0x17, 0x98,
+    /// ];
+    /// assert_eq!(
+    ///     Address::from_public_key(&compressed).unwrap().to_string(),
+    ///     "0x7E5F4552091A69125d5DfCb7b8C2659029395Bdf",
+    /// );
+    /// ```
+    pub fn from_public_key(key: &[u8]) -> Result<Self, PublicKeyError> {
+        let mut buffer = [0_u8; 65];
+        let sec1 = match key.len() {
+            33 | 65 => key,
+            64 => {
+                buffer[0] = 0x04;
+                buffer[1..].copy_from_slice(key);
+                &buffer[..]
+            }
+            _ => return Err(PublicKeyError::InvalidLength),
+        };
+
+        let point = PublicKey::from_sec1_bytes(sec1)
+            .map_err(|_| PublicKeyError::InvalidPoint)?
+            .to_encoded_point(false);
+        let digest = checksum::keccak256(&point.as_bytes()[1..]);
+        Ok(Self::from_slice(&digest[12..]))
+    }
+}
+
+/// Represents an error deriving an address from a public key.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum PublicKeyError {
+    /// The public key does not have a supported length.
+    InvalidLength,
+    /// The public key is not a valid secp256k1 curve point.
+    InvalidPoint,
+}
+
+impl Display for PublicKeyError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Self::InvalidLength => write!(f, "invalid public key length"),
+            Self::InvalidPoint => write!(f, "public key is not a valid curve point"),
+        }
+    }
+}
+
+impl core::error::Error for PublicKeyError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use k256::SecretKey;
+
+    #[test]
+    fn public_key_encodings() {
+        let secret = SecretKey::from_slice(&[0x01; 32]).unwrap();
+        let compressed = secret.public_key().to_encoded_point(true);
+        let uncompressed = secret.public_key().to_encoded_point(false);
+
+        let expected = "0x1a642f0E3c3aF545E7AcBD38b07251B3990914F1"
+            .parse::<Address>()
+            .unwrap();
+        for key in [
+            compressed.as_bytes(),
+            uncompressed.as_bytes(),
+            &uncompressed.as_bytes()[1..],
+        ] {
+            assert_eq!(Address::from_public_key(key).unwrap(), expected);
+        }
+    }
+
+    #[test]
+    fn invalid_public_keys() {
+        assert_eq!(
+            Address::from_public_key(&[0x04; 32]),
+            Err(PublicKeyError::InvalidLength),
+        );
+        let mut compressed = [0; 33];
+        compressed[0] = 0x02;
+        assert_eq!(
+            Address::from_public_key(&compressed),
+            Err(PublicKeyError::InvalidPoint),
+        );
+        assert_eq!(
+            Address::from_public_key(&[0xff; 64]),
+            Err(PublicKeyError::InvalidPoint),
+        );
+    }
+}
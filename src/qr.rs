@@ -0,0 +1,915 @@
+//! QR code encoding of addresses and payment request URIs.
+//!
+//! This is a small `no_std` QR code encoder supporting byte mode data in
+//! versions 1 through 10, which is enough for checksummed addresses and most
+//! EIP-681 payment request URIs. Just like address formatting, encoding does
+//! not allocate and the resulting module matrix is stored in a fixed-size
+//! buffer on the stack.
+
+use crate::{checksum, Address};
+use core::fmt::{self, Display, Formatter, Write as _};
+
+/// The maximum supported QR code version.
+const MAX_VERSION: usize = 10;
+
+/// The size in modules of the largest supported QR code version.
+const MAX_SIZE: usize = MAX_VERSION * 4 + 17;
+
+/// The number of bytes needed to store the modules of the largest supported
+/// QR code version.
+const MAX_MODULES_LEN: usize = (MAX_SIZE * MAX_SIZE).div_ceil(8);
+
+/// The maximum number of codewords of the supported QR code versions.
+const MAX_CODEWORDS: usize = raw_data_modules(MAX_VERSION) / 8;
+
+/// The maximum number of error correction codewords per block.
+const MAX_ECC_LEN: usize = 30;
+
+/// Error correction codewords per block, indexed by error correction level
+/// and version.
+const ECC_CODEWORDS_PER_BLOCK: [[u8; MAX_VERSION + 1]; 4] = [
+    [0, 7, 10, 15, 20, 26, 18, 20, 24, 30, 18],
+    [0, 10, 16, 26, 18, 24, 16, 18, 22, 22, 26],
+    [0, 13, 22, 18, 26, 18, 24, 18, 22, 20, 24],
+    [0, 17, 28, 22, 16, 22, 28, 26, 26, 24, 28],
+];
+
+/// Number of error correction blocks, indexed by error correction level and
+/// version.
+const NUM_ERROR_CORRECTION_BLOCKS: [[u8; MAX_VERSION + 1]; 4] = [
+    [0, 1, 1, 1, 1, 1, 2, 2, 2, 2, 4],
+    [0, 1, 1, 1, 2, 2, 4, 4, 4, 5, 5],
+    [0, 1, 1, 2, 2, 4, 4, 6, 6, 8, 8],
+    [0, 1, 1, 2, 4, 4, 4, 5, 6, 8, 8],
+];
+
+impl Address {
+    /// Encodes the address as a QR code. The address is encoded with its
+    /// EIP-55 checksum.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// # use ethaddr::{qr::EcLevel, Address};
+    /// let qr = Address([0xee; 20]).to_qr_code(EcLevel::Medium);
+    /// assert_eq!(qr.version(), 3);
+    /// assert_eq!(qr.size(), 29);
+    /// ```
+    pub fn to_qr_code(&self, ec: EcLevel) -> QrCode {
+        // Addresses always fit even at the highest error correction level.
+        QrCode::encode(checksum::fmt(self).as_str().as_bytes(), ec).unwrap()
+    }
+}
+
+/// QR code error correction level.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum EcLevel {
+    /// Recovers from approximately 7% of erroneous codewords.
+    Low,
+    /// Recovers from approximately 15% of erroneous codewords.
+    Medium,
+    /// Recovers from approximately 25% of erroneous codewords.
+    Quartile,
+    /// Recovers from approximately 30% of erroneous codewords.
+    High,
+}
+
+impl EcLevel {
+    /// Returns the format bits of the error correction level.
+    fn format_bits(self) -> u32 {
+        match self {
+            Self::Low => 1,
+            Self::Medium => 0,
+            Self::Quartile => 3,
+            Self::High => 2,
+        }
+    }
+}
+
+/// A QR code module matrix.
+#[derive(Clone, Copy, Eq, Hash, PartialEq)]
+pub struct QrCode {
+    version: u8,
+    modules: [u8; MAX_MODULES_LEN],
+}
+
+impl QrCode {
+    /// Encodes data as a QR code in byte mode, using the smallest version that
+    /// fits the data at the specified error correction level. The mask pattern
+    /// is chosen automatically to minimize the QR code penalty score.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// # use ethaddr::qr::{EcLevel, QrCode, QrError};
+    /// let qr = QrCode::encode(b"hello", EcLevel::Low).unwrap();
+    /// assert_eq!(qr.version(), 1);
+    /// assert_eq!(
+    ///     QrCode::encode(&[0; 512], EcLevel::Low),
+    ///     Err(QrError::DataTooLong),
+    /// );
+    /// ```
+    pub fn encode(data: &[u8], ec: EcLevel) -> Result<Self, QrError> {
+        Self::encode_inner(data, ec, None)
+    }
+
+    /// Encodes data as a QR code in byte mode with an explicit mask pattern.
+    ///
+    /// # Panics
+    ///
+    /// This method panics if the mask pattern is not in the range `0..8`.
+    pub fn encode_with_mask(data: &[u8], ec: EcLevel, mask: u8) -> Result<Self, QrError> {
+        assert!(mask < 8, "invalid QR code mask pattern");
+        Self::encode_inner(data, ec, Some(mask))
+    }
+
+    /// Returns the QR code version, between 1 and 10.
+    pub fn version(&self) -> u8 {
+        self.version
+    }
+
+    /// Returns the width and height of the QR code in modules.
+    pub fn size(&self) -> usize {
+        self.version as usize * 4 + 17
+    }
+
+    /// Returns `true` if the module at the specified coordinates is dark.
+    /// Coordinates outside of the QR code are light.
+    pub fn get(&self, x: usize, y: usize) -> bool {
+        let size = self.size();
+        x < size && y < size && bit(&self.modules, y * size + x)
+    }
+
+    /// Returns a renderer for the QR code as an SVG image, with the specified
+    /// quiet zone border in modules.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// # use ethaddr::qr::{EcLevel, QrCode};
+    /// let qr = QrCode::encode(b"hello", EcLevel::Low).unwrap();
+    /// let svg = qr.svg(4).to_string();
+    /// assert!(svg.starts_with("<svg "));
+    /// ```
+    pub fn svg(&self, border: usize) -> Svg<'_> {
+        Svg { qr: self, border }
+    }
+
+    /// Returns a renderer for the QR code as plain text, with the specified
+    /// quiet zone border in modules.
+    ///
+    /// Two rows of modules are rendered per line with Unicode block elements,
+    /// where dark modules are drawn with the foreground color. This means
+    /// that the text needs to be displayed as dark text on a light background
+    /// for the QR code to be scannable.
+    pub fn text(&self, border: usize) -> Text<'_> {
+        Text { qr: self, border }
+    }
+
+    fn encode_inner(data: &[u8], ec: EcLevel, mask: Option<u8>) -> Result<Self, QrError> {
+        let (version, data_len) = (1..=MAX_VERSION)
+            .map(|version| (version, data_codewords(version, ec)))
+            .find(|(version, data_len)| {
+                let count_bits = if *version < 10 { 8 } else { 16 };
+                4 + count_bits + data.len() * 8 <= data_len * 8
+            })
+            .ok_or(QrError::DataTooLong)?;
+
+        // Encode the data in byte mode, followed by the terminator and the
+        // padding bytes.
+        let mut codewords = BitBuffer::new();
+        codewords.push(0b0100, 4);
+        codewords.push(data.len() as u32, if version < 10 { 8 } else { 16 });
+        for byte in data {
+            codewords.push(*byte as u32, 8);
+        }
+        codewords.push(0, (data_len * 8 - codewords.len).min(4));
+        codewords.push(0, (8 - codewords.len % 8) % 8);
+        for pad in [0xec, 0x11].into_iter().cycle() {
+            if codewords.len == data_len * 8 {
+                break;
+            }
+            codewords.push(pad, 8);
+        }
+
+        let mut builder = Builder::new(version);
+        builder.draw_function_patterns();
+        builder.draw_codewords(&interleave(&codewords.bytes[..data_len], version, ec));
+
+        let mask = mask.unwrap_or_else(|| {
+            let mut best = (0, i32::MAX);
+            for mask in 0..8 {
+                builder.apply_mask(mask);
+                builder.draw_format_bits(ec, mask);
+                let penalty = builder.penalty();
+                if penalty < best.1 {
+                    best = (mask, penalty);
+                }
+                builder.apply_mask(mask);
+            }
+            best.0
+        });
+        builder.apply_mask(mask);
+        builder.draw_format_bits(ec, mask);
+
+        Ok(Self {
+            version: version as _,
+            modules: builder.modules,
+        })
+    }
+}
+
+impl fmt::Debug for QrCode {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.debug_struct("QrCode")
+            .field("version", &self.version)
+            .finish_non_exhaustive()
+    }
+}
+
+/// An SVG renderer for a QR code.
+#[derive(Clone, Copy, Debug)]
+pub struct Svg<'a> {
+    qr: &'a QrCode,
+    border: usize,
+}
+
+impl Display for Svg<'_> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        let (size, border) = (self.qr.size(), self.border);
+        let dimension = size.saturating_add(border.saturating_mul(2));
+        write!(
+            f,
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" version=\"1.1\" \
+             viewBox=\"0 0 {dimension} {dimension}\" stroke=\"none\">\
+             <rect width=\"100%\" height=\"100%\" fill=\"#FFFFFF\"/>\
+             <path d=\"",
+        )?;
+        let mut first = true;
+        for y in 0..size {
+            for x in 0..size {
+                if self.qr.get(x, y) {
+                    if !first {
+                        f.write_char(' ')?;
+                    }
+                    write!(
+                        f,
+                        "M{},{}h1v1h-1z",
+                        x.saturating_add(border),
+                        y.saturating_add(border),
+                    )?;
+                    first = false;
+                }
+            }
+        }
+        write!(f, "\" fill=\"#000000\"/></svg>")
+    }
+}
+
+/// A plain text renderer for a QR code.
+#[derive(Clone, Copy, Debug)]
+pub struct Text<'a> {
+    qr: &'a QrCode,
+    border: usize,
+}
+
+impl Display for Text<'_> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        let (size, border) = (self.qr.size(), self.border);
+        let dimension = size.saturating_add(border.saturating_mul(2));
+        let module =
+            |x: usize, y: usize| x >= border && y >= border && self.qr.get(x - border, y - border);
+        for y in (0..dimension).step_by(2) {
+            if y > 0 {
+                f.write_char('\n')?;
+            }
+            for x in 0..dimension {
+                f.write_char(match (module(x, y), module(x, y + 1)) {
+                    (true, true) => '█',
+                    (true, false) => '▀',
+                    (false, true) => '▄',
+                    (false, false) => ' ',
+                })?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Represents an error encoding a QR code.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum QrError {
+    /// The data does not fit in the largest supported QR code version.
+    DataTooLong,
+}
+
+impl Display for QrError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Self::DataTooLong => write!(f, "data too long for QR code"),
+        }
+    }
+}
+
+impl core::error::Error for QrError {}
+
+/// Returns the number of modules available for data and error correction
+/// codewords, including remainder bits, for a QR code version.
+const fn raw_data_modules(version: usize) -> usize {
+    let mut result = (16 * version + 128) * version + 64;
+    if version >= 2 {
+        let num_align = version / 7 + 2;
+        result -= (25 * num_align - 10) * num_align - 55;
+        if version >= 7 {
+            result -= 36;
+        }
+    }
+    result
+}
+
+/// Returns the number of data codewords for a QR code version and error
+/// correction level.
+fn data_codewords(version: usize, ec: EcLevel) -> usize {
+    raw_data_modules(version) / 8
+        - ECC_CODEWORDS_PER_BLOCK[ec as usize][version] as usize
+            * NUM_ERROR_CORRECTION_BLOCKS[ec as usize][version] as usize
+}
+
+/// Splits the data codewords into blocks, computes the error correction
+/// codewords for each block and interleaves them.
+fn interleave(data: &[u8], version: usize, ec: EcLevel) -> Codewords {
+    let num_blocks = NUM_ERROR_CORRECTION_BLOCKS[ec as usize][version] as usize;
+    let ecc_len = ECC_CODEWORDS_PER_BLOCK[ec as usize][version] as usize;
+    let raw_codewords = raw_data_modules(version) / 8;
+    let num_short_blocks = num_blocks - raw_codewords % num_blocks;
+    let short_data_len = raw_codewords / num_blocks - ecc_len;
+
+    // Short blocks come first, and long blocks have one more data codeword.
+    let block = |i: usize| {
+        let start = i * short_data_len + i.saturating_sub(num_short_blocks);
+        let len = short_data_len + usize::from(i >= num_short_blocks);
+        &data[start..start + len]
+    };
+
+    let divisor = reed_solomon_divisor(ecc_len);
+    let mut ecc = [0; MAX_CODEWORDS];
+    for i in 0..num_blocks {
+        reed_solomon_remainder(
+            block(i),
+            &divisor[..ecc_len],
+            &mut ecc[i * ecc_len..][..ecc_len],
+        );
+    }
+
+    let mut result = Codewords {
+        bytes: [0; MAX_CODEWORDS],
+        len: 0,
+    };
+    for i in 0..=short_data_len {
+        for j in 0..num_blocks {
+            if let Some(byte) = block(j).get(i) {
+                result.push(*byte);
+            }
+        }
+    }
+    for i in 0..ecc_len {
+        for j in 0..num_blocks {
+            result.push(ecc[j * ecc_len + i]);
+        }
+    }
+    result
+}
+
+/// Computes the Reed-Solomon generator polynomial of the specified degree.
+fn reed_solomon_divisor(degree: usize) -> [u8; MAX_ECC_LEN] {
+    let mut result = [0; MAX_ECC_LEN];
+    result[degree - 1] = 1;
+    let mut root = 1;
+    for _ in 0..degree {
+        for j in 0..degree {
+            result[j] = gf_mul(result[j], root);
+            if j + 1 < degree {
+                result[j] ^= result[j + 1];
+            }
+        }
+        root = gf_mul(root, 0x02);
+    }
+    result
+}
+
+/// Computes the Reed-Solomon error correction codewords for some data.
+fn reed_solomon_remainder(data: &[u8], divisor: &[u8], result: &mut [u8]) {
+    result.fill(0);
+    for byte in data {
+        let factor = byte ^ result[0];
+        result.copy_within(1.., 0);
+        *result.last_mut().unwrap() = 0;
+        for (x, y) in result.iter_mut().zip(divisor) {
+            *x ^= gf_mul(*y, factor);
+        }
+    }
+}
+
+/// Multiplies two elements of GF(2^8) modulo `x^8 + x^4 + x^3 + x^2 + 1`.
+fn gf_mul(x: u8, y: u8) -> u8 {
+    let mut z = 0_u32;
+    for i in (0..8).rev() {
+        z = (z << 1) ^ ((z >> 7) * 0x11d);
+        z ^= ((y as u32 >> i) & 1) * x as u32;
+    }
+    z as u8
+}
+
+/// Returns the bit at the specified index of a big-endian bit string.
+fn bit(bytes: &[u8], i: usize) -> bool {
+    bytes[i / 8] & (0x80 >> (i % 8)) != 0
+}
+
+/// Sets the bit at the specified index of a big-endian bit string.
+fn set_bit(bytes: &mut [u8], i: usize, value: bool) {
+    if value {
+        bytes[i / 8] |= 0x80 >> (i % 8);
+    } else {
+        bytes[i / 8] &= !(0x80 >> (i % 8));
+    }
+}
+
+/// A buffer of data bits.
+struct BitBuffer {
+    bytes: [u8; MAX_CODEWORDS],
+    len: usize,
+}
+
+impl BitBuffer {
+    fn new() -> Self {
+        Self {
+            bytes: [0; MAX_CODEWORDS],
+            len: 0,
+        }
+    }
+
+    fn push(&mut self, value: u32, bits: usize) {
+        for i in (0..bits).rev() {
+            set_bit(&mut self.bytes, self.len, (value >> i) & 1 != 0);
+            self.len += 1;
+        }
+    }
+}
+
+/// A buffer of interleaved codewords.
+struct Codewords {
+    bytes: [u8; MAX_CODEWORDS],
+    len: usize,
+}
+
+impl Codewords {
+    fn push(&mut self, byte: u8) {
+        self.bytes[self.len] = byte;
+        self.len += 1;
+    }
+}
+
+/// A QR code module matrix under construction, keeping track of which
+/// modules are part of function patterns.
+struct Builder {
+    version: usize,
+    size: usize,
+    modules: [u8; MAX_MODULES_LEN],
+    functions: [u8; MAX_MODULES_LEN],
+}
+
+impl Builder {
+    fn new(version: usize) -> Self {
+        Self {
+            version,
+            size: version * 4 + 17,
+            modules: [0; MAX_MODULES_LEN],
+            functions: [0; MAX_MODULES_LEN],
+        }
+    }
+
+    fn get(&self, x: usize, y: usize) -> bool {
+        bit(&self.modules, y * self.size + x)
+    }
+
+    fn is_function(&self, x: usize, y: usize) -> bool {
+        bit(&self.functions, y * self.size + x)
+    }
+
+    fn set(&mut self, x: usize, y: usize, dark: bool) {
+        set_bit(&mut self.modules, y * self.size + x, dark);
+    }
+
+    fn set_function(&mut self, x: usize, y: usize, dark: bool) {
+        self.set(x, y, dark);
+        set_bit(&mut self.functions, y * self.size + x, true);
+    }
+
+    fn draw_function_patterns(&mut self) {
+        let size = self.size;
+        for i in 0..size {
+            self.set_function(6, i, i % 2 == 0);
+            self.set_function(i, 6, i % 2 == 0);
+        }
+
+        self.draw_finder_pattern(3, 3);
+        self.draw_finder_pattern(size - 4, 3);
+        self.draw_finder_pattern(3, size - 4);
+
+        // Alignment patterns are drawn everywhere on the grid of positions,
+        // except where they would overlap with the finder patterns.
+        let (positions, len) = self.alignment_pattern_positions();
+        for i in 0..len {
+            for j in 0..len {
+                if (i == 0 && (j == 0 || j == len - 1)) || (i == len - 1 && j == 0) {
+                    continue;
+                }
+                self.draw_alignment_pattern(positions[i], positions[j]);
+            }
+        }
+
+        // Reserve the format bits, they get drawn after masking.
+        self.draw_format_bits(EcLevel::Low, 0);
+        self.draw_version();
+    }
+
+    fn draw_finder_pattern(&mut self, x: usize, y: usize) {
+        for dy in -4_isize..=4 {
+            for dx in -4_isize..=4 {
+                let distance = dx.abs().max(dy.abs());
+                let (xx, yy) = (x as isize + dx, y as isize + dy);
+                if (0..self.size as isize).contains(&xx) && (0..self.size as isize).contains(&yy) {
+                    self.set_function(xx as _, yy as _, distance != 2 && distance != 4);
+                }
+            }
+        }
+    }
+
+    fn draw_alignment_pattern(&mut self, x: usize, y: usize) {
+        for dy in -2_isize..=2 {
+            for dx in -2_isize..=2 {
+                let distance = dx.abs().max(dy.abs());
+                self.set_function(
+                    (x as isize + dx) as _,
+                    (y as isize + dy) as _,
+                    distance != 1,
+                );
+            }
+        }
+    }
+
+    fn alignment_pattern_positions(&self) -> ([usize; 3], usize) {
+        let mut result = [0; 3];
+        if self.version == 1 {
+            return (result, 0);
+        }
+        let num_align = self.version / 7 + 2;
+        let step = (self.version * 8 + num_align * 3 + 5) / (num_align * 4 - 4) * 2;
+        result[0] = 6;
+        for i in 1..num_align {
+            result[num_align - i] = self.size - 7 - (i - 1) * step;
+        }
+        (result, num_align)
+    }
+
+    fn draw_format_bits(&mut self, ec: EcLevel, mask: u8) {
+        let data = (ec.format_bits() << 3) | mask as u32;
+        let mut rem = data;
+        for _ in 0..10 {
+            rem = (rem << 1) ^ ((rem >> 9) * 0x537);
+        }
+        let bits = ((data << 10) | rem) ^ 0x5412;
+        let bit = |i: usize| (bits >> i) & 1 != 0;
+
+        // First copy, around the top left finder pattern.
+        for i in 0..6 {
+            self.set_function(8, i, bit(i));
+        }
+        self.set_function(8, 7, bit(6));
+        self.set_function(8, 8, bit(7));
+        self.set_function(7, 8, bit(8));
+        for i in 9..15 {
+            self.set_function(14 - i, 8, bit(i));
+        }
+
+        // Second copy, split between the top right and bottom left finder
+        // patterns, along with the dark module.
+        let size = self.size;
+        for i in 0..8 {
+            self.set_function(size - 1 - i, 8, bit(i));
+        }
+        for i in 8..15 {
+            self.set_function(8, size - 15 + i, bit(i));
+        }
+        self.set_function(8, size - 8, true);
+    }
+
+    fn draw_version(&mut self) {
+        if self.version < 7 {
+            return;
+        }
+
+        let mut rem = self.version as u32;
+        for _ in 0..12 {
+            rem = (rem << 1) ^ ((rem >> 11) * 0x1f25);
+        }
+        let bits = ((self.version as u32) << 12) | rem;
+        for i in 0..18 {
+            let dark = (bits >> i) & 1 != 0;
+            let (a, b) = (self.size - 11 + i % 3, i / 3);
+            self.set_function(a, b, dark);
+            self.set_function(b, a, dark);
+        }
+    }
+
+    fn draw_codewords(&mut self, codewords: &Codewords) {
+        let data = &codewords.bytes[..codewords.len];
+        let mut i = 0;
+
+        // Codewords are placed in a zigzag pattern of two-module wide
+        // columns, from right to left, skipping the vertical timing pattern.
+        let mut right = self.size - 1;
+        while right >= 1 {
+            if right == 6 {
+                right = 5;
+            }
+            for vertical in 0..self.size {
+                for j in 0..2 {
+                    let x = right - j;
+                    let upward = (right + 1) & 2 == 0;
+                    let y = if upward {
+                        self.size - 1 - vertical
+                    } else {
+                        vertical
+                    };
+                    if !self.is_function(x, y) && i < data.len() * 8 {
+                        self.set(x, y, bit(data, i));
+                        i += 1;
+                    }
+                }
+            }
+            if right < 2 {
+                break;
+            }
+            right -= 2;
+        }
+    }
+
+    fn apply_mask(&mut self, mask: u8) {
+        for y in 0..self.size {
+            for x in 0..self.size {
+                let invert = match mask {
+                    0 => (x + y) % 2 == 0,
+                    1 => y % 2 == 0,
+                    2 => x % 3 == 0,
+                    3 => (x + y) % 3 == 0,
+                    4 => (x / 3 + y / 2) % 2 == 0,
+                    5 => x * y % 2 + x * y % 3 == 0,
+                    6 => (x * y % 2 + x * y % 3) % 2 == 0,
+                    _ => ((x + y) % 2 + x * y % 3) % 2 == 0,
+                };
+                if invert && !self.is_function(x, y) {
+                    let dark = self.get(x, y);
+                    self.set(x, y, !dark);
+                }
+            }
+        }
+    }
+
+    /// Computes the penalty score used for choosing the mask pattern.
+    fn penalty(&self) -> i32 {
+        let size = self.size;
+        let mut result = 0;
+
+        // Runs of same-colored modules and finder-like patterns in rows and
+        // columns.
+        for transpose in [false, true] {
+            for y in 0..size {
+                let mut run = Run::new(size);
+                for x in 0..size {
+                    let dark = if transpose {
+                        self.get(y, x)
+                    } else {
+                        self.get(x, y)
+                    };
+                    result += run.push(dark);
+                }
+                result += run.terminate();
+            }
+        }
+
+        // 2x2 blocks of same-colored modules.
+        for y in 0..size - 1 {
+            for x in 0..size - 1 {
+                let dark = self.get(x, y);
+                if dark == self.get(x + 1, y)
+                    && dark == self.get(x, y + 1)
+                    && dark == self.get(x + 1, y + 1)
+                {
+                    result += 3;
+                }
+            }
+        }
+
+        // Balance of dark and light modules.
+        let dark = (0..size * size).filter(|i| bit(&self.modules, *i)).count() as i32;
+        let total = (size * size) as i32;
+        let k = ((dark * 20 - total * 10).abs() + total - 1) / total - 1;
+        result + k * 10
+    }
+}
+
+/// Run length tracking for computing the penalty score of a row or column.
+struct Run {
+    size: usize,
+    dark: bool,
+    len: usize,
+    history: [usize; 7],
+}
+
+impl Run {
+    fn new(size: usize) -> Self {
+        Self {
+            size,
+            dark: false,
+            len: 0,
+            history: [0; 7],
+        }
+    }
+
+    fn push(&mut self, dark: bool) -> i32 {
+        let mut result = 0;
+        if dark == self.dark {
+            self.len += 1;
+            if self.len == 5 {
+                result += 3;
+            } else if self.len > 5 {
+                result += 1;
+            }
+        } else {
+            self.add_history(self.len);
+            if !self.dark {
+                result += self.count_patterns() * 40;
+            }
+            self.dark = dark;
+            self.len = 1;
+        }
+        result
+    }
+
+    fn terminate(&mut self) -> i32 {
+        let mut len = self.len;
+        if self.dark {
+            self.add_history(len);
+            len = 0;
+        }
+        // Add the light border to the final run.
+        self.add_history(len + self.size);
+        self.count_patterns() * 40
+    }
+
+    fn add_history(&mut self, mut len: usize) {
+        // Add the light border to the initial run.
+        if self.history[0] == 0 {
+            len += self.size;
+        }
+        self.history.copy_within(0..6, 1);
+        self.history[0] = len;
+    }
+
+    fn count_patterns(&self) -> i32 {
+        let h = &self.history;
+        let n = h[1];
+        let core = n > 0 && h[2] == n && h[3] == n * 3 && h[4] == n && h[5] == n;
+        i32::from(core && h[0] >= n * 4 && h[6] >= n)
+            + i32::from(core && h[6] >= n * 4 && h[0] >= n)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use qrcodegen::{QrCodeEcc, QrSegment, Version};
+
+    fn reference(
+        data: &[u8],
+        ec: EcLevel,
+        mask: Option<u8>,
+    ) -> Result<qrcodegen::QrCode, qrcodegen::DataTooLong> {
+        let ecl = match ec {
+            EcLevel::Low => QrCodeEcc::Low,
+            EcLevel::Medium => QrCodeEcc::Medium,
+            EcLevel::Quartile => QrCodeEcc::Quartile,
+            EcLevel::High => QrCodeEcc::High,
+        };
+        qrcodegen::QrCode::encode_segments_advanced(
+            &[QrSegment::make_bytes(data)],
+            ecl,
+            Version::MIN,
+            Version::new(MAX_VERSION as _),
+            mask.map(qrcodegen::Mask::new),
+            false,
+        )
+    }
+
+    fn assert_matches_reference(qr: &QrCode, reference: &qrcodegen::QrCode) {
+        assert_eq!(qr.version(), reference.version().value());
+        assert_eq!(qr.size() as i32, reference.size());
+        for y in 0..qr.size() {
+            for x in 0..qr.size() {
+                assert_eq!(qr.get(x, y), reference.get_module(x as _, y as _));
+            }
+        }
+    }
+
+    #[test]
+    fn matches_reference_encoder() {
+        let data = (0..272_u32).map(|i| (i * 31) as u8).collect::<Vec<_>>();
+        for ec in [
+            EcLevel::Low,
+            EcLevel::Medium,
+            EcLevel::Quartile,
+            EcLevel::High,
+        ] {
+            for len in (0..=271).step_by(7) {
+                let (data, mask) = (&data[..len], (len % 8) as u8);
+                match QrCode::encode_with_mask(data, ec, mask) {
+                    Ok(qr) => {
+                        assert_matches_reference(&qr, &reference(data, ec, Some(mask)).unwrap())
+                    }
+                    Err(_) => assert!(reference(data, ec, Some(mask)).is_err()),
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn automatic_mask_matches_reference_encoder() {
+        for (data, ec) in [
+            (&b"hello"[..], EcLevel::Low),
+            (
+                b"0xEeeeeEeeeEeEeeEeEeEeeEEEeeeeEeeeeeeeEEeE",
+                EcLevel::Medium,
+            ),
+            (b"0x90F8bf6A479f320ead074411a4B0e7944Ea8c9C1", EcLevel::High),
+            (
+                b"ethereum:0xEeeeeEeeeEeEeeEeEeEeeEEEeeeeEeeeeeeeEEeE@1/transfer\
+                  ?address=0x90F8bf6A479f320ead074411a4B0e7944Ea8c9C1&uint256=1e6",
+                EcLevel::Quartile,
+            ),
+        ] {
+            let qr = QrCode::encode(data, ec).unwrap();
+            assert_matches_reference(&qr, &reference(data, ec, None).unwrap());
+        }
+    }
+
+    #[test]
+    fn data_too_long() {
+        assert!(QrCode::encode(&[0; 271], EcLevel::Low).is_ok());
+        assert_eq!(
+            QrCode::encode(&[0; 272], EcLevel::Low),
+            Err(QrError::DataTooLong),
+        );
+    }
+
+    #[test]
+    fn address_qr_code() {
+        let address = Address([0xee; 20]);
+        let qr = address.to_qr_code(EcLevel::Medium);
+        assert_eq!(
+            qr,
+            QrCode::encode(address.to_string().as_bytes(), EcLevel::Medium).unwrap(),
+        );
+    }
+
+    #[test]
+    fn renderers() {
+        let qr = QrCode::encode(b"hello", EcLevel::Low).unwrap();
+
+        let text = qr.text(1).to_string();
+        let lines = text.lines().collect::<Vec<_>>();
+        assert_eq!(lines.len(), 12);
+        assert!(lines.iter().all(|line| line.chars().count() == 23));
+        // The first line has the top border and the top row of the finder
+        // patterns, which are separated by light modules.
+        let finder = format!(" {} ", "▄".repeat(7));
+        assert!(lines[0].starts_with(&finder) && lines[0].ends_with(&finder));
+
+        let svg = qr.svg(4).to_string();
+        assert!(svg.starts_with(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" version=\"1.1\" viewBox=\"0 0 29 29\""
+        ));
+        assert!(svg.contains("M4,4h1v1h-1z M5,4h1v1h-1z"));
+        assert!(svg.ends_with("\" fill=\"#000000\"/></svg>"));
+
+        // Huge borders must not overflow.
+        let svg = qr.svg(usize::MAX).to_string();
+        assert!(svg.contains(&format!("viewBox=\"0 0 {0} {0}\"", usize::MAX)));
+    }
+}
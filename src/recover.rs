@@ -0,0 +1,231 @@
+//! Recovery of Ethereum public addresses from ECDSA signatures.
+
+use crate::Address;
+use core::fmt::{self, Display, Formatter};
+use k256::ecdsa::{RecoveryId, Signature, VerifyingKey};
+
+impl Address {
+    /// Recovers the address of the signer of a 32-byte message hash.
+    ///
+    /// The signature can be specified in any of the following encodings:
+    /// - 65-byte `r || s || v` signature, where `v` is a recovery ID in
+    ///   `{0, 1}`, `{27, 28}` or an EIP-155 `{chain_id * 2 + 35, chain_id * 2 +
+    ///   36}` value
+    /// - `r || s || v` signatures longer than 65 bytes, where `v` is encoded as
+    ///   a big-endian integer; this allows for EIP-155 `v` values for chain IDs
+    ///   that do not fit in a single byte
+    /// - 64-byte EIP-2098 compact `r || yParityAndS` signature
+    ///
+    /// Signatures with high `s` values are rejected, as specified in EIP-2.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// # use ethaddr::Address;
+    /// let message_hash = [
+    ///     0xda, 0xf5, 0xa7, 0x79, 0xae, 0x97, 0x2f, 0x97, 0x21, 0x97, 0x30,
+    ///     0x3d, 0x7b, 0x57, 0x47, 0x46, 0xc7, 0xef, 0x83, 0xea, 0xda, 0xc0,
+    ///     0xf2, 0x79, 0x1a, 0xd2, 0x3d, 0xb9, 0x2e, 0x4c, 0x8e, 0x53,
+    /// ];
+    /// let signature = [
+    ///     0x28, 0xef, 0x61, 0x34, 0x0b, 0xd9, 0x39, 0xbc, 0x21, 0x95, 0xfe,
+    ///     0x53, 0x75, 0x67, 0x86, 0x60, 0x03, 0xe1, 0xa1, 0x5d, 0x3c, 0x71,
+    ///     0xff, 0x63, 0xe1, 0x59, 0x06, 0x20, 0xaa, 0x63, 0x62, 0x76, 0x67,
+    ///     0xcb, 0xe9, 0xd8, 0x99, 0x7f, 0x76, 0x1a, 0xec, 0xb7, 0x03, 0x30,
+    ///     0x4b, 0x38, 0x00, 0xcc, 0xf5, 0x55, 0xc9, 0xf3, 0xdc, 0x64, 0x21,
+    ///     0x4b, 0x29, 0x7f, 0xb1, 0x96, 0x6a, 0x3b, 0x6d, 0x83, 0x25,
+    /// ];
+    /// assert_eq!(
+    ///     Address::recover(message_hash, &signature).unwrap().to_string(),
+    ///     "0x9d8A62f656a8d1615C1294fd71e9CFb3E4855A4F",
+    /// );
+    /// ```
+    pub fn recover(prehash: [u8; 32], signature: &[u8]) -> Result<Self, RecoverError> {
+        let (r, s, y_parity) = match signature.len() {
+            64 => {
+                let mut s = [0_u8; 32];
+                s.copy_from_slice(&signature[32..64]);
+                let y_parity = s[0] >> 7;
+                s[0] &= 0x7f;
+                (&signature[..32], s, y_parity)
+            }
+            65..=72 => {
+                let v = signature[64..]
+                    .iter()
+                    .fold(0_u64, |v, byte| (v << 8) + *byte as u64);
+                let mut s = [0_u8; 32];
+                s.copy_from_slice(&signature[32..64]);
+                (&signature[..32], s, y_parity(v)?)
+            }
+            _ => return Err(RecoverError::InvalidLength),
+        };
+
+        let mut r_bytes = [0_u8; 32];
+        r_bytes.copy_from_slice(r);
+        recover(&prehash, r_bytes, s, y_parity)
+    }
+}
+
+/// Recovers the signer address from an ECDSA signature's `r`, `s` and
+/// `y_parity` components.
+pub fn recover(
+    prehash: &[u8; 32],
+    r: [u8; 32],
+    s: [u8; 32],
+    y_parity: u8,
+) -> Result<Address, RecoverError> {
+    let signature = Signature::from_scalars(r, s).map_err(|_| RecoverError::InvalidSignature)?;
+    if signature.normalize_s().is_some() {
+        return Err(RecoverError::HighS);
+    }
+    recover_signature(prehash, &signature, y_parity)
+}
+
+/// Recovers the signer address from a signature with a low `s` value.
+pub(crate) fn recover_signature(
+    prehash: &[u8; 32],
+    signature: &Signature,
+    y_parity: u8,
+) -> Result<Address, RecoverError> {
+    let recovery_id =
+        RecoveryId::from_byte(y_parity).ok_or(RecoverError::InvalidRecoveryId(y_parity as _))?;
+
+    let key = VerifyingKey::recover_from_prehash(prehash, signature, recovery_id)
+        .map_err(|_| RecoverError::InvalidPoint)?;
+    Address::from_public_key(key.to_encoded_point(false).as_bytes())
+        .map_err(|_| RecoverError::InvalidPoint)
+}
+
+/// Computes the `y` parity of a signature from its `v` value.
+pub fn y_parity(v: u64) -> Result<u8, RecoverError> {
+    match v {
+        0 | 1 => Ok(v as _),
+        27 | 28 => Ok((v - 27) as _),
+        35.. => Ok(((v - 35) % 2) as _),
+        _ => Err(RecoverError::InvalidRecoveryId(v)),
+    }
+}
+
+/// Represents an error recovering an address from a signature.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum RecoverError {
+    /// The signature does not have a supported length.
+    InvalidLength,
+    /// The signature's recovery ID (`v` value) is invalid.
+    InvalidRecoveryId(u64),
+    /// The signature's `r` or `s` values are not valid scalars.
+    InvalidSignature,
+    /// The signature's `s` value is in the upper half of the curve order and
+    /// is not accepted, as specified in EIP-2.
+    HighS,
+    /// The signature does not recover to a valid curve point.
+    InvalidPoint,
+}
+
+impl Display for RecoverError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Self::InvalidLength => write!(f, "invalid signature length"),
+            Self::InvalidRecoveryId(v) => write!(f, "invalid signature recovery ID {v}"),
+            Self::InvalidSignature => write!(f, "invalid signature"),
+            Self::HighS => write!(f, "signature `s` value is not canonical"),
+            Self::InvalidPoint => write!(f, "signature does not recover to a valid point"),
+        }
+    }
+}
+
+impl core::error::Error for RecoverError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::bytes32;
+    use k256::ecdsa::SigningKey;
+
+    fn sign(key: &SigningKey, prehash: &[u8; 32]) -> ([u8; 64], u8) {
+        let (signature, recovery_id) = key.sign_prehash_recoverable(prehash).unwrap();
+        (signature.to_bytes().into(), recovery_id.to_byte())
+    }
+
+    fn signer(key: &SigningKey) -> Address {
+        Address::from_public_key(key.verifying_key().to_encoded_point(false).as_bytes()).unwrap()
+    }
+
+    #[test]
+    fn recover_signature_encodings() {
+        let key = SigningKey::from_slice(&[0x01; 32]).unwrap();
+        let prehash = [0x42; 32];
+        let (rs, y_parity) = sign(&key, &prehash);
+
+        let sepolia = (11155111 * 2 + 35 + y_parity as u64).to_be_bytes();
+        for v in [
+            &[y_parity][..],
+            &[y_parity + 27],
+            &[y_parity + 37],
+            &sepolia[4..],
+        ] {
+            let signature = [&rs[..], v].concat();
+            assert_eq!(Address::recover(prehash, &signature), Ok(signer(&key)));
+        }
+
+        let mut compact = rs;
+        compact[32] |= y_parity << 7;
+        assert_eq!(Address::recover(prehash, &compact), Ok(signer(&key)));
+    }
+
+    #[test]
+    fn recover_eip2098_vectors() {
+        let signer = "0x2e988A386a799F506693793c6A5AF6B54dfAaBfB"
+            .parse::<Address>()
+            .unwrap();
+        for (prehash, r, y_parity_and_s) in [
+            // `personal_sign("Hello World")`
+            (
+                "a1de988600a42c4b4ab089b619297c17d53cffae5d5120d82d8a92d0bb3b78f2",
+                "68a020a209d3d56c46f38cc50a33f704f4a9a10a59377f8dd762ac66910e9b90",
+                "7e865ad05c4035ab5792787d4a0297a43617ae897930a6fe4d822b8faea52064",
+            ),
+            // `personal_sign("It's a small(er) world")`
+            (
+                "ac33ec93c768b669bdb542a85baebaf7342d35fc9ad8fc0bbc1b852c6f8bf021",
+                "9328da16089fcba9bececa81663203989f2df5fe1faa6291a45381c81bd17f76",
+                "939c6d6b623b42da56557e5e734a43dc83345ddfadec52cbe24d0cc64f550793",
+            ),
+        ] {
+            let signature = [bytes32(r), bytes32(y_parity_and_s)].concat();
+            assert_eq!(Address::recover(bytes32(prehash), &signature), Ok(signer));
+        }
+    }
+
+    #[test]
+    fn recover_errors() {
+        let key = SigningKey::from_slice(&[0x01; 32]).unwrap();
+        let prehash = [0x42; 32];
+        let (rs, _) = sign(&key, &prehash);
+
+        assert_eq!(
+            Address::recover(prehash, &rs[..63]),
+            Err(RecoverError::InvalidLength),
+        );
+        for v in [2, 26, 29, 34] {
+            assert_eq!(
+                Address::recover(prehash, &[&rs[..], &[v]].concat()),
+                Err(RecoverError::InvalidRecoveryId(v as _)),
+            );
+        }
+        assert_eq!(
+            Address::recover(prehash, &[0; 65]),
+            Err(RecoverError::InvalidSignature),
+        );
+
+        // Negate `s` to get the equivalent high-`s` signature.
+        let signature = Signature::from_slice(&rs).unwrap();
+        let high_s = Signature::from_scalars(signature.r(), -*signature.s()).unwrap();
+        assert_eq!(
+            Address::recover(prehash, &[&high_s.to_bytes()[..], &[27]].concat()),
+            Err(RecoverError::HighS),
+        );
+    }
+}
@@ -1,6 +1,6 @@
 //! Serde serialization implementation for Ethereum public addresses.
 
-use crate::Address;
+use crate::{caip::ChainAccount, Address, Parser, Prefix};
 use core::fmt::{self, Formatter};
 use serde::{
     de::{self, Deserializer, Visitor},
@@ -30,9 +30,9 @@ impl<'de> Visitor<'de> for AddressVisitor {
     where
         E: de::Error,
     {
-        s.strip_prefix("0x")
-            .ok_or_else(|| de::Error::custom("missing `0x`-prefix"))?
-            .parse()
+        Parser::new()
+            .prefix(Prefix::Required)
+            .parse(s)
             .map_err(de::Error::custom)
     }
 }
@@ -47,6 +47,41 @@ impl Serialize for Address {
     }
 }
 
+impl<'de> Deserialize<'de> for ChainAccount {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_str(ChainAccountVisitor)
+    }
+}
+
+struct ChainAccountVisitor;
+
+impl<'de> Visitor<'de> for ChainAccountVisitor {
+    type Value = ChainAccount;
+
+    fn expecting(&self, f: &mut Formatter) -> fmt::Result {
+        f.write_str("a CAIP-10 account ID")
+    }
+
+    fn visit_str<E>(self, s: &str) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        s.parse().map_err(de::Error::custom)
+    }
+}
+
+impl Serialize for ChainAccount {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.collect_str(self)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -73,4 +108,18 @@ mod tests {
         let deserializer = BorrowedStrDeserializer::<value::Error>::new(without_prefix);
         assert!(Address::deserialize(deserializer).is_err());
     }
+
+    #[test]
+    fn deserialize_chain_account() {
+        let deserializer = BorrowedStrDeserializer::<value::Error>::new(
+            "eip155:1:0xEeeeeEeeeEeEeeEeEeEeeEEEeeeeEeeeeeeeEEeE",
+        );
+        assert_eq!(
+            ChainAccount::deserialize(deserializer).unwrap(),
+            ChainAccount::eip155(1, Address([0xee; 20])),
+        );
+
+        let deserializer = BorrowedStrDeserializer::<value::Error>::new("eip155:1");
+        assert!(ChainAccount::deserialize(deserializer).is_err());
+    }
 }
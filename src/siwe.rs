@@ -0,0 +1,806 @@
+//! Sign-In with Ethereum (EIP-4361) message parsing and verification.
+
+use crate::{eip191, Address, ParseAddressError, RecoverError};
+use std::{
+    fmt::{self, Display, Formatter},
+    iter::Peekable,
+    str::{FromStr, Split},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+/// A Sign-In with Ethereum message.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```
+/// # use ethaddr::siwe::Message;
+/// let message = "\
+/// service.invalid wants you to sign in with your Ethereum account:
+/// 0xC02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc2
+///
+/// I accept the ServiceOrg Terms of Service: https://service.invalid/tos
+///
+/// URI: https://service.invalid/login
+/// Version: 1
+/// Chain ID: 1
+/// Nonce: 32891756
+/// Issued At: 2021-09-30T16:25:24Z";
+///
+/// let parsed = message.parse::<Message>().unwrap();
+/// assert_eq!(parsed.domain, "service.invalid");
+/// assert_eq!(parsed.chain_id, 1);
+/// assert_eq!(parsed.to_string(), message);
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Message {
+    /// The URI scheme of the origin of the request.
+    pub scheme: Option<String>,
+    /// The RFC 3986 authority requesting the signing.
+    pub domain: String,
+    /// The address performing the signing.
+    pub address: Address,
+    /// A human-readable assertion that the user will sign.
+    pub statement: Option<String>,
+    /// The RFC 3986 URI referring to the resource that is the subject of the
+    /// signing.
+    pub uri: String,
+    /// The version of the message, which must be `1`.
+    pub version: String,
+    /// The EIP-155 chain ID to which the session is bound.
+    pub chain_id: u64,
+    /// A randomized token used to prevent replay attacks, at least 8
+    /// alphanumeric characters.
+    pub nonce: String,
+    /// The time when the message was generated.
+    pub issued_at: Timestamp,
+    /// The time when the signed authentication message is no longer valid.
+    pub expiration_time: Option<Timestamp>,
+    /// The time when the signed authentication message will become valid.
+    pub not_before: Option<Timestamp>,
+    /// A system-specific identifier that may be used to uniquely refer to the
+    /// sign-in request.
+    pub request_id: Option<String>,
+    /// A list of URIs the user wishes to have resolved as part of
+    /// authentication by the relying party.
+    pub resources: Vec<String>,
+}
+
+/// The suffix of the first line of a message.
+const HEADER: &str = " wants you to sign in with your Ethereum account:";
+
+impl Message {
+    /// Returns the EIP-191 personal message hash of the message, which is what
+    /// gets signed by the user.
+    pub fn signing_hash(&self) -> [u8; 32] {
+        eip191::personal_message_hash(self.to_string().as_bytes())
+    }
+
+    /// Verifies a signature for the message.
+    ///
+    /// This verifies that the signature was signed by the message's address,
+    /// that the message is valid at the current (or specified) time, and that
+    /// the message matches any of the expected domain, nonce and chain ID
+    /// specified in the `options`.
+    pub fn verify(
+        &self,
+        signature: &[u8],
+        options: &VerificationOptions,
+    ) -> Result<(), VerificationError> {
+        if options.domain.is_some_and(|domain| domain != self.domain) {
+            return Err(VerificationError::DomainMismatch);
+        }
+        if options.nonce.is_some_and(|nonce| nonce != self.nonce) {
+            return Err(VerificationError::NonceMismatch);
+        }
+        if options
+            .chain_id
+            .is_some_and(|chain_id| chain_id != self.chain_id)
+        {
+            return Err(VerificationError::ChainIdMismatch);
+        }
+
+        let time = Timestamp::from_system_time(options.time.unwrap_or_else(SystemTime::now));
+        if self
+            .expiration_time
+            .as_ref()
+            .is_some_and(|expiration_time| time.instant() >= expiration_time.instant())
+        {
+            return Err(VerificationError::Expired);
+        }
+        if self
+            .not_before
+            .as_ref()
+            .is_some_and(|not_before| time.instant() < not_before.instant())
+        {
+            return Err(VerificationError::NotYetValid);
+        }
+
+        let signer = Address::recover(self.signing_hash(), signature)
+            .map_err(VerificationError::Signature)?;
+        if signer != self.address {
+            return Err(VerificationError::SignerMismatch(signer));
+        }
+
+        Ok(())
+    }
+}
+
+impl Display for Message {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        if let Some(scheme) = &self.scheme {
+            write!(f, "{scheme}://")?;
+        }
+        writeln!(f, "{}{HEADER}", self.domain)?;
+        writeln!(f, "{}", self.address)?;
+        writeln!(f)?;
+        if let Some(statement) = &self.statement {
+            writeln!(f, "{statement}")?;
+        }
+        writeln!(f)?;
+        writeln!(f, "URI: {}", self.uri)?;
+        writeln!(f, "Version: {}", self.version)?;
+        writeln!(f, "Chain ID: {}", self.chain_id)?;
+        writeln!(f, "Nonce: {}", self.nonce)?;
+        write!(f, "Issued At: {}", self.issued_at)?;
+        if let Some(expiration_time) = &self.expiration_time {
+            write!(f, "\nExpiration Time: {expiration_time}")?;
+        }
+        if let Some(not_before) = &self.not_before {
+            write!(f, "\nNot Before: {not_before}")?;
+        }
+        if let Some(request_id) = &self.request_id {
+            write!(f, "\nRequest ID: {request_id}")?;
+        }
+        if !self.resources.is_empty() {
+            write!(f, "\nResources:")?;
+            for resource in &self.resources {
+                write!(f, "\n- {resource}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for Message {
+    type Err = ParseMessageError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut lines = Lines(s.split('\n').peekable());
+
+        let origin = lines
+            .next()?
+            .strip_suffix(HEADER)
+            .ok_or(ParseMessageError::InvalidHeader)?;
+        let (scheme, domain) = match origin.split_once("://") {
+            Some((scheme, domain)) => (Some(scheme), domain),
+            None => (None, origin),
+        };
+        if scheme.is_some_and(|scheme| !is_scheme(scheme)) || !is_uri_part(domain) {
+            return Err(ParseMessageError::InvalidHeader);
+        }
+
+        let address = lines.next()?;
+        if !address.starts_with("0x") {
+            return Err(ParseMessageError::InvalidField("address"));
+        }
+        let address =
+            Address::from_str_checksum(address).map_err(ParseMessageError::InvalidAddress)?;
+
+        lines.empty()?;
+        let statement = match lines.next()? {
+            "" => None,
+            statement => {
+                lines.empty()?;
+                Some(statement)
+            }
+        };
+
+        let uri = lines.field("URI", is_uri_part)?;
+        let version = lines.field("Version", |version| version == "1")?;
+        let chain_id = lines
+            .field("Chain ID", |chain_id| {
+                !chain_id.is_empty() && chain_id.bytes().all(|b| b.is_ascii_digit())
+            })?
+            .parse()
+            .map_err(|_| ParseMessageError::InvalidField("Chain ID"))?;
+        let nonce = lines.field("Nonce", |nonce| {
+            nonce.len() >= 8 && nonce.bytes().all(|b| b.is_ascii_alphanumeric())
+        })?;
+        let issued_at = lines.timestamp("Issued At")?;
+        let expiration_time = lines.optional(Lines::timestamp, "Expiration Time")?;
+        let not_before = lines.optional(Lines::timestamp, "Not Before")?;
+        let request_id = lines.optional(|lines, name| lines.field(name, |_| true), "Request ID")?;
+
+        let mut resources = Vec::new();
+        if lines.0.next_if_eq(&"Resources:").is_some() {
+            for resource in lines.0.by_ref() {
+                resources.push(
+                    resource
+                        .strip_prefix("- ")
+                        .filter(|resource| is_uri_part(resource))
+                        .ok_or(ParseMessageError::InvalidField("Resources"))?
+                        .to_owned(),
+                );
+            }
+        }
+        if lines.0.next().is_some() {
+            return Err(ParseMessageError::UnexpectedContent);
+        }
+
+        Ok(Self {
+            scheme: scheme.map(str::to_owned),
+            domain: domain.to_owned(),
+            address,
+            statement: statement.map(str::to_owned),
+            uri: uri.to_owned(),
+            version: version.to_owned(),
+            chain_id,
+            nonce: nonce.to_owned(),
+            issued_at,
+            expiration_time,
+            not_before,
+            request_id: request_id.map(str::to_owned),
+            resources,
+        })
+    }
+}
+
+/// Message line parser.
+struct Lines<'a>(Peekable<Split<'a, char>>);
+
+impl<'a> Lines<'a> {
+    fn next(&mut self) -> Result<&'a str, ParseMessageError> {
+        self.0.next().ok_or(ParseMessageError::UnexpectedEnd)
+    }
+
+    fn empty(&mut self) -> Result<(), ParseMessageError> {
+        match self.next()? {
+            "" => Ok(()),
+            _ => Err(ParseMessageError::UnexpectedContent),
+        }
+    }
+
+    fn field(
+        &mut self,
+        name: &'static str,
+        valid: impl FnOnce(&str) -> bool,
+    ) -> Result<&'a str, ParseMessageError> {
+        let value = self
+            .next()?
+            .strip_prefix(name)
+            .and_then(|line| line.strip_prefix(": "))
+            .ok_or(ParseMessageError::MissingField(name))?;
+        if !valid(value) {
+            return Err(ParseMessageError::InvalidField(name));
+        }
+        Ok(value)
+    }
+
+    fn timestamp(&mut self, name: &'static str) -> Result<Timestamp, ParseMessageError> {
+        self.field(name, |_| true)?
+            .parse()
+            .map_err(|_| ParseMessageError::InvalidField(name))
+    }
+
+    fn optional<T>(
+        &mut self,
+        parse: impl FnOnce(&mut Self, &'static str) -> Result<T, ParseMessageError>,
+        name: &'static str,
+    ) -> Result<Option<T>, ParseMessageError> {
+        match self.0.peek() {
+            Some(line) if line.starts_with(name) => parse(self, name).map(Some),
+            _ => Ok(None),
+        }
+    }
+}
+
+/// Returns whether a string is a valid RFC 3986 URI scheme.
+fn is_scheme(scheme: &str) -> bool {
+    scheme.starts_with(|c: char| c.is_ascii_alphabetic())
+        && scheme
+            .bytes()
+            .all(|b| b.is_ascii_alphanumeric() || matches!(b, b'+' | b'-' | b'.'))
+}
+
+/// Returns whether a string is a plausible URI or URI authority component.
+///
+/// This only checks that the string is non-empty and does not contain any
+/// whitespace or control characters, and does not do full RFC 3986
+/// validation.
+fn is_uri_part(s: &str) -> bool {
+    !s.is_empty() && s.bytes().all(|b| b.is_ascii_graphic() || b >= 0x80)
+}
+
+/// An RFC 3339 timestamp.
+///
+/// The timestamp keeps its original string representation, so that messages
+/// are formatted exactly as they were signed.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Timestamp {
+    repr: String,
+    seconds: i64,
+    nanos: u32,
+}
+
+impl Timestamp {
+    /// Creates a UTC timestamp from a system time.
+    pub fn from_system_time(time: SystemTime) -> Self {
+        let (seconds, nanos) = match time.duration_since(UNIX_EPOCH) {
+            Ok(duration) => (duration.as_secs() as i64, duration.subsec_nanos()),
+            Err(err) => {
+                let duration = err.duration();
+                match duration.subsec_nanos() {
+                    0 => (-(duration.as_secs() as i64), 0),
+                    nanos => (-(duration.as_secs() as i64) - 1, 1_000_000_000 - nanos),
+                }
+            }
+        };
+
+        let days = seconds.div_euclid(86400);
+        let time = seconds.rem_euclid(86400);
+        let (year, month, day) = civil_from_days(days);
+        let mut repr = format!(
+            "{year:04}-{month:02}-{day:02}T{:02}:{:02}:{:02}",
+            time / 3600,
+            time / 60 % 60,
+            time % 60,
+        );
+        if nanos > 0 {
+            repr.push_str(format!(".{nanos:09}").trim_end_matches('0'));
+        }
+        repr.push('Z');
+
+        Self {
+            repr,
+            seconds,
+            nanos,
+        }
+    }
+
+    /// Returns the number of seconds since the Unix epoch.
+    pub fn unix_timestamp(&self) -> i64 {
+        self.seconds
+    }
+
+    /// Returns the timestamp as a string.
+    pub fn as_str(&self) -> &str {
+        &self.repr
+    }
+
+    /// Returns the instant in time represented by the timestamp, for
+    /// comparisons.
+    fn instant(&self) -> (i64, u32) {
+        (self.seconds, self.nanos)
+    }
+}
+
+impl Display for Timestamp {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.pad(&self.repr)
+    }
+}
+
+impl FromStr for Timestamp {
+    type Err = ParseMessageError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || ParseMessageError::InvalidField("timestamp");
+        let number = |range: std::ops::Range<usize>| {
+            s.get(range)
+                .filter(|digits| digits.bytes().all(|b| b.is_ascii_digit()))
+                .and_then(|digits| digits.parse::<i64>().ok())
+                .ok_or_else(invalid)
+        };
+        let separator = |i: usize, expected: &[u8]| match s.as_bytes().get(i) {
+            Some(b) if expected.contains(b) => Ok(()),
+            _ => Err(invalid()),
+        };
+
+        let (year, month, day) = (number(0..4)?, number(5..7)?, number(8..10)?);
+        let (hour, minute, second) = (number(11..13)?, number(14..16)?, number(17..19)?);
+        separator(4, b"-")?;
+        separator(7, b"-")?;
+        separator(10, b"Tt")?;
+        separator(13, b":")?;
+        separator(16, b":")?;
+
+        let mut rest = &s[19..];
+        let mut nanos = 0_u32;
+        if let Some(fraction) = rest.strip_prefix('.') {
+            let len = fraction
+                .bytes()
+                .position(|b| !b.is_ascii_digit())
+                .unwrap_or(fraction.len());
+            if len == 0 {
+                return Err(invalid());
+            }
+            for (i, digit) in fraction.bytes().take(len).enumerate() {
+                if i < 9 {
+                    nanos += (digit - b'0') as u32 * 10_u32.pow(8 - i as u32);
+                }
+            }
+            rest = &fraction[len..];
+        }
+        let offset = match rest {
+            "Z" | "z" => 0,
+            _ => {
+                let sign = match rest.as_bytes().first() {
+                    Some(b'+') => 1,
+                    Some(b'-') => -1,
+                    _ => return Err(invalid()),
+                };
+                let offset = parse_offset(rest).ok_or_else(invalid)?;
+                sign * offset
+            }
+        };
+
+        let days_in_month = match month {
+            1 | 3 | 5 | 7 | 8 | 10 | 12 => 31,
+            4 | 6 | 9 | 11 => 30,
+            2 if year % 4 == 0 && (year % 100 != 0 || year % 400 == 0) => 29,
+            2 => 28,
+            _ => return Err(invalid()),
+        };
+        // Allow leap seconds, as specified in RFC 3339.
+        if day < 1 || day > days_in_month || hour > 23 || minute > 59 || second > 60 {
+            return Err(invalid());
+        }
+
+        let days = days_from_civil(year, month, day);
+        let seconds = days * 86400 + hour * 3600 + minute * 60 + second - offset;
+        Ok(Self {
+            repr: s.to_owned(),
+            seconds,
+            nanos,
+        })
+    }
+}
+
+/// Parses a `+HH:MM` or `-HH:MM` time zone offset into seconds, ignoring the
+/// sign.
+fn parse_offset(s: &str) -> Option<i64> {
+    let (hours, minutes) = s.get(1..)?.split_once(':')?;
+    let digits = |n: &str| n.len() == 2 && n.bytes().all(|b| b.is_ascii_digit());
+    if !digits(hours) || !digits(minutes) {
+        return None;
+    }
+    let (hours, minutes) = (hours.parse::<i64>().ok()?, minutes.parse::<i64>().ok()?);
+    (hours <= 23 && minutes <= 59).then_some(hours * 3600 + minutes * 60)
+}
+
+/// Computes the number of days since the Unix epoch of a proleptic Gregorian
+/// calendar date.
+///
+/// See <http://howardhinnant.github.io/date_algorithms.html#days_from_civil>.
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let yoe = year - era * 400;
+    let doy = (153 * (month + if month > 2 { -3 } else { 9 }) + 2) / 5 + day - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146097 + doe - 719468
+}
+
+/// Computes the proleptic Gregorian calendar date of a number of days since
+/// the Unix epoch.
+///
+/// See <http://howardhinnant.github.io/date_algorithms.html#civil_from_days>.
+fn civil_from_days(days: i64) -> (i64, i64, i64) {
+    let days = days + 719468;
+    let era = days.div_euclid(146097);
+    let doe = days - era * 146097;
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + (month <= 2) as i64;
+    (year, month, day)
+}
+
+/// Options for verifying a Sign-In with Ethereum message.
+#[derive(Clone, Debug, Default)]
+pub struct VerificationOptions<'a> {
+    /// The expected domain of the message.
+    pub domain: Option<&'a str>,
+    /// The expected nonce of the message.
+    pub nonce: Option<&'a str>,
+    /// The expected chain ID of the message.
+    pub chain_id: Option<u64>,
+    /// The time at which to check the message's validity. Defaults to the
+    /// current system time.
+    pub time: Option<SystemTime>,
+}
+
+/// Represents an error parsing a Sign-In with Ethereum message.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ParseMessageError {
+    /// The first line of the message is not a valid sign-in request.
+    InvalidHeader,
+    /// The message's address is invalid or does not have a valid EIP-55
+    /// checksum.
+    InvalidAddress(ParseAddressError),
+    /// A required message field is missing.
+    MissingField(&'static str),
+    /// A message field has an invalid value.
+    InvalidField(&'static str),
+    /// The message contains unexpected content.
+    UnexpectedContent,
+    /// The message ended unexpectedly.
+    UnexpectedEnd,
+}
+
+impl Display for ParseMessageError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Self::InvalidHeader => write!(f, "invalid sign-in message header"),
+            Self::InvalidAddress(err) => write!(f, "invalid sign-in message address: {err}"),
+            Self::MissingField(name) => write!(f, "missing sign-in message field `{name}`"),
+            Self::InvalidField(name) => write!(f, "invalid sign-in message field `{name}`"),
+            Self::UnexpectedContent => write!(f, "unexpected sign-in message content"),
+            Self::UnexpectedEnd => write!(f, "unexpected end of sign-in message"),
+        }
+    }
+}
+
+impl std::error::Error for ParseMessageError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::InvalidAddress(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+/// Represents an error verifying a Sign-In with Ethereum message.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum VerificationError {
+    /// The signature is invalid.
+    Signature(RecoverError),
+    /// The signature was signed by a different address than the message's.
+    SignerMismatch(Address),
+    /// The message's domain does not match the expected domain.
+    DomainMismatch,
+    /// The message's nonce does not match the expected nonce.
+    NonceMismatch,
+    /// The message's chain ID does not match the expected chain ID.
+    ChainIdMismatch,
+    /// The message has expired.
+    Expired,
+    /// The message is not yet valid.
+    NotYetValid,
+}
+
+impl Display for VerificationError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Self::Signature(err) => write!(f, "invalid sign-in signature: {err}"),
+            Self::SignerMismatch(signer) => {
+                write!(f, "sign-in message signed by unexpected address {signer}")
+            }
+            Self::DomainMismatch => write!(f, "sign-in message domain mismatch"),
+            Self::NonceMismatch => write!(f, "sign-in message nonce mismatch"),
+            Self::ChainIdMismatch => write!(f, "sign-in message chain ID mismatch"),
+            Self::Expired => write!(f, "sign-in message has expired"),
+            Self::NotYetValid => write!(f, "sign-in message is not yet valid"),
+        }
+    }
+}
+
+impl std::error::Error for VerificationError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Signature(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::MismatchedIndices;
+    use k256::ecdsa::SigningKey;
+    use std::time::Duration;
+
+    const MESSAGE: &str = "\
+https://service.invalid wants you to sign in with your Ethereum account:
+0x1a642f0E3c3aF545E7AcBD38b07251B3990914F1
+
+I accept the ServiceOrg Terms of Service: https://service.invalid/tos
+
+URI: https://service.invalid/login
+Version: 1
+Chain ID: 1
+Nonce: 32891756
+Issued At: 2021-09-30T16:25:24Z
+Expiration Time: 2021-10-01T16:25:24.000+02:00
+Not Before: 2021-09-30T16:25:24Z
+Request ID: some-request
+Resources:
+- ipfs://bafybeiemxf5abjwjbikoz4mc3a3dla6ual3jsgpdr4cjr3oz3evfyavhwq/
+- https://example.com/my-web2-claim.json";
+
+    fn sign(message: &Message) -> Vec<u8> {
+        let key = SigningKey::from_slice(&[0x01; 32]).unwrap();
+        let (signature, recovery_id) = key
+            .sign_prehash_recoverable(&message.signing_hash())
+            .unwrap();
+        [&signature.to_bytes()[..], &[recovery_id.to_byte() + 27]].concat()
+    }
+
+    fn at(timestamp: &str) -> Option<SystemTime> {
+        let seconds = timestamp.parse::<Timestamp>().unwrap().unix_timestamp();
+        Some(UNIX_EPOCH + Duration::from_secs(seconds as _))
+    }
+
+    #[test]
+    fn parse_and_format_message() {
+        let message = MESSAGE.parse::<Message>().unwrap();
+        assert_eq!(message.scheme.as_deref(), Some("https"));
+        assert_eq!(message.domain, "service.invalid");
+        assert_eq!(
+            message.statement.as_deref(),
+            Some("I accept the ServiceOrg Terms of Service: https://service.invalid/tos"),
+        );
+        assert_eq!(message.issued_at.unix_timestamp(), 1633019124);
+        assert_eq!(
+            message.expiration_time.as_ref().unwrap().unix_timestamp(),
+            1633019124 + 86400 - 7200,
+        );
+        assert_eq!(message.resources.len(), 2);
+        assert_eq!(message.to_string(), MESSAGE);
+
+        let minimal = "\
+service.invalid wants you to sign in with your Ethereum account:
+0x1a642f0E3c3aF545E7AcBD38b07251B3990914F1
+
+
+URI: https://service.invalid/login
+Version: 1
+Chain ID: 1
+Nonce: 32891756
+Issued At: 2021-09-30T16:25:24Z";
+        let message = minimal.parse::<Message>().unwrap();
+        assert_eq!(message.statement, None);
+        assert_eq!(message.to_string(), minimal);
+    }
+
+    #[test]
+    fn parse_invalid_messages() {
+        for (message, err) in [
+            (
+                MESSAGE.replace(
+                    "0x1a642f0E3c3aF545E7AcBD38b07251B3990914F1",
+                    "0x1a642f0e3c3af545e7acbd38b07251b3990914f1",
+                ),
+                ParseMessageError::InvalidAddress(ParseAddressError::ChecksumMismatch {
+                    expected: Address::from_str("0x1a642f0E3c3aF545E7AcBD38b07251B3990914F1")
+                        .unwrap()
+                        .to_checksum(),
+                    index: 2,
+                    mismatches: MismatchedIndices::new(2, [9, 14, 18, 20, 22, 23, 32, 40]),
+                }),
+            ),
+            (
+                MESSAGE.replace("0x1a642f0E3c3aF545E7AcBD38b07251B3990914F1", "0x1a642f"),
+                ParseMessageError::InvalidAddress(ParseAddressError::InvalidLength {
+                    len: 6,
+                    prefixed: true,
+                }),
+            ),
+            (
+                MESSAGE.replace(" wants you", " would like you"),
+                ParseMessageError::InvalidHeader,
+            ),
+            (
+                MESSAGE.replace("Version: 1", "Version: 2"),
+                ParseMessageError::InvalidField("Version"),
+            ),
+            (
+                MESSAGE.replace("Nonce: 32891756", "Nonce: 1234"),
+                ParseMessageError::InvalidField("Nonce"),
+            ),
+            (
+                MESSAGE.replace("Chain ID: 1\n", ""),
+                ParseMessageError::MissingField("Chain ID"),
+            ),
+            (
+                MESSAGE.replace("2021-09-30T16:25:24Z\nExp", "2021-02-30T16:25:24Z\nExp"),
+                ParseMessageError::InvalidField("Issued At"),
+            ),
+            (
+                format!("{MESSAGE}\nExtra"),
+                ParseMessageError::InvalidField("Resources"),
+            ),
+        ] {
+            assert_eq!(message.parse::<Message>(), Err(err));
+        }
+    }
+
+    #[test]
+    fn verify_message() {
+        let message = MESSAGE.parse::<Message>().unwrap();
+        let signature = sign(&message);
+        let options = VerificationOptions {
+            domain: Some("service.invalid"),
+            nonce: Some("32891756"),
+            chain_id: Some(1),
+            time: at("2021-10-01T00:00:00Z"),
+        };
+
+        assert_eq!(message.verify(&signature, &options), Ok(()));
+        for (options, err) in [
+            (
+                VerificationOptions {
+                    domain: Some("attacker.invalid"),
+                    ..options.clone()
+                },
+                VerificationError::DomainMismatch,
+            ),
+            (
+                VerificationOptions {
+                    nonce: Some("00000000"),
+                    ..options.clone()
+                },
+                VerificationError::NonceMismatch,
+            ),
+            (
+                VerificationOptions {
+                    chain_id: Some(100),
+                    ..options.clone()
+                },
+                VerificationError::ChainIdMismatch,
+            ),
+            (
+                VerificationOptions {
+                    time: at("2021-10-01T14:25:24Z"),
+                    ..options.clone()
+                },
+                VerificationError::Expired,
+            ),
+            (
+                VerificationOptions {
+                    time: at("2021-09-30T16:25:23Z"),
+                    ..options.clone()
+                },
+                VerificationError::NotYetValid,
+            ),
+        ] {
+            assert_eq!(message.verify(&signature, &options), Err(err));
+        }
+
+        let mut other = message.clone();
+        other.nonce = "00000000".to_owned();
+        assert!(matches!(
+            other.verify(
+                &signature,
+                &VerificationOptions {
+                    time: options.time,
+                    ..Default::default()
+                }
+            ),
+            Err(VerificationError::SignerMismatch(_)),
+        ));
+    }
+
+    #[test]
+    fn timestamps() {
+        for (s, seconds) in [
+            ("1970-01-01T00:00:00Z", 0),
+            ("2000-02-29T12:00:00+01:00", 951822000),
+            ("1969-12-31T23:59:59.5Z", -1),
+        ] {
+            let timestamp = s.parse::<Timestamp>().unwrap();
+            assert_eq!(timestamp.unix_timestamp(), seconds);
+        }
+
+        let time = UNIX_EPOCH + Duration::new(1633019124, 500_000_000);
+        assert_eq!(
+            Timestamp::from_system_time(time).as_str(),
+            "2021-09-30T16:25:24.5Z",
+        );
+    }
+}
@@ -0,0 +1,828 @@
+//! Recovery of sender addresses from signed raw transactions.
+//!
+//! Signed transactions do not include the address of their sender. Instead,
+//! it is recovered from the transaction signature and its signing hash. This
+//! module decodes just enough of RLP-encoded raw transactions to compute
+//! their signing hash, and supports the following transaction types:
+//! - Legacy transactions, both with and without EIP-155 replay protection
+//! - EIP-2930 access list transactions (type `0x01`)
+//! - EIP-1559 fee market transactions (type `0x02`)
+//! - EIP-4844 blob transactions (type `0x03`), both in their canonical form
+//!   and in their network form with blobs, commitments and proofs
+//! - EIP-7702 set code transactions (type `0x04`)
+
+use crate::{checksum, recover, Address, RecoverError};
+use k256::ecdsa::Signature;
+use std::fmt::{self, Display, Formatter};
+
+/// A decoded signed transaction.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```
+/// # use ethaddr::transaction::{Transaction, TransactionType};
+/// let raw = [
+///     0xf8, 0x6c, 0x09, 0x85, 0x04, 0xa8, 0x17, 0xc8, 0x00, 0x82, 0x52, 0x08,
+///     0x94, 0x35, 0x35, 0x35, 0x35, 0x35, 0x35, 0x35, 0x35, 0x35, 0x35, 0x35,
+///     0x35, 0x35, 0x35, 0x35, 0x35, 0x35, 0x35, 0x35, 0x35, 0x88, 0x0d, 0xe0,
+///     0xb6, 0xb3, 0xa7, 0x64, 0x00, 0x00, 0x80, 0x25, 0xa0, 0x28, 0xef, 0x61,
+///     0x34, 0x0b, 0xd9, 0x39, 0xbc, 0x21, 0x95, 0xfe, 0x53, 0x75, 0x67, 0x86,
+///     0x60, 0x03, 0xe1, 0xa1, 0x5d, 0x3c, 0x71, 0xff, 0x63, 0xe1, 0x59, 0x06,
+///     0x20, 0xaa, 0x63, 0x62, 0x76, 0xa0, 0x67, 0xcb, 0xe9, 0xd8, 0x99, 0x7f,
+///     0x76, 0x1a, 0xec, 0xb7, 0x03, 0x30, 0x4b, 0x38, 0x00, 0xcc, 0xf5, 0x55,
+///     0xc9, 0xf3, 0xdc, 0x64, 0x21, 0x4b, 0x29, 0x7f, 0xb1, 0x96, 0x6a, 0x3b,
+///     0x6d, 0x83,
+/// ];
+/// let transaction = Transaction::decode(&raw).unwrap();
+/// assert_eq!(transaction.kind, TransactionType::Legacy);
+/// assert_eq!(transaction.chain_id, Some(1));
+/// assert_eq!(
+///     transaction.from.to_string(),
+///     "0x9d8A62f656a8d1615C1294fd71e9CFb3E4855A4F",
+/// );
+/// assert_eq!(
+///     transaction.to.unwrap().to_string(),
+///     "0x3535353535353535353535353535353535353535",
+/// );
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Transaction {
+    /// The transaction type.
+    pub kind: TransactionType,
+    /// The chain ID of the transaction, or `None` for legacy transactions
+    /// without EIP-155 replay protection.
+    pub chain_id: Option<u64>,
+    /// The recovered sender of the transaction.
+    pub from: Address,
+    /// The recipient of the transaction, or `None` for contract creations.
+    pub to: Option<Address>,
+    /// The transaction hash.
+    pub hash: [u8; 32],
+    /// The hash that was signed by the sender.
+    pub signing_hash: [u8; 32],
+}
+
+/// The type of a transaction.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum TransactionType {
+    /// Legacy transaction.
+    Legacy,
+    /// EIP-2930 access list transaction.
+    Eip2930,
+    /// EIP-1559 fee market transaction.
+    Eip1559,
+    /// EIP-4844 blob transaction.
+    Eip4844,
+    /// EIP-7702 set code transaction.
+    Eip7702,
+}
+
+impl TransactionType {
+    /// Returns the EIP-2718 transaction type byte, or `None` for legacy
+    /// transactions.
+    pub fn type_byte(&self) -> Option<u8> {
+        match self {
+            Self::Legacy => None,
+            Self::Eip2930 => Some(0x01),
+            Self::Eip1559 => Some(0x02),
+            Self::Eip4844 => Some(0x03),
+            Self::Eip7702 => Some(0x04),
+        }
+    }
+
+    /// Returns the number of RLP list fields of a signed transaction of this
+    /// type and the index of its `to` field.
+    fn layout(&self) -> (usize, usize) {
+        match self {
+            Self::Legacy => (9, 3),
+            Self::Eip2930 => (11, 4),
+            Self::Eip1559 => (12, 5),
+            Self::Eip4844 => (14, 5),
+            Self::Eip7702 => (13, 5),
+        }
+    }
+}
+
+impl Transaction {
+    /// Decodes a signed raw transaction and recovers its sender.
+    ///
+    /// Signatures with high `s` values are rejected, as they have not been
+    /// valid since EIP-2 in the Homestead hard fork. See
+    /// [`Transaction::decode_lenient`] for decoding historical transactions.
+    pub fn decode(raw: &[u8]) -> Result<Self, TransactionError> {
+        Self::decode_with(raw, false)
+    }
+
+    /// Decodes a signed raw transaction and recovers its sender, accepting
+    /// signatures with high `s` values for legacy transactions without replay
+    /// protection.
+    ///
+    /// This is only needed for transactions from before the Homestead hard
+    /// fork, and should not be used for new transactions, which the network
+    /// rejects if they have high `s` values.
+    pub fn decode_lenient(raw: &[u8]) -> Result<Self, TransactionError> {
+        Self::decode_with(raw, true)
+    }
+
+    /// Decodes a signed raw transaction, optionally accepting high `s` values
+    /// for legacy transactions without replay protection.
+    fn decode_with(raw: &[u8], high_s: bool) -> Result<Self, TransactionError> {
+        let (kind, payload) = match raw.first() {
+            Some(0xc0..) => (TransactionType::Legacy, raw),
+            Some(0x01) => (TransactionType::Eip2930, &raw[1..]),
+            Some(0x02) => (TransactionType::Eip1559, &raw[1..]),
+            Some(0x03) => (TransactionType::Eip4844, &raw[1..]),
+            Some(0x04) => (TransactionType::Eip7702, &raw[1..]),
+            Some(ty) => return Err(TransactionError::UnsupportedType(*ty)),
+            None => return Err(TransactionError::InvalidRlp),
+        };
+
+        let mut list = Item::decode_all(payload)?.list()?;
+        if kind == TransactionType::Eip4844 && list.first().is_some_and(|item| item.is_list) {
+            // EIP-4844 network form: `[tx_payload_body, blobs, commitments,
+            // proofs]`.
+            if list.len() != 4 {
+                return Err(TransactionError::InvalidFieldCount);
+            }
+            list = list[0].list()?;
+        }
+
+        let (len, to_index) = kind.layout();
+        if list.len() != len {
+            return Err(TransactionError::InvalidFieldCount);
+        }
+        let (fields, signature) = list.split_at(len - 3);
+
+        let to = match fields[to_index].string()? {
+            [] if matches!(kind, TransactionType::Eip4844 | TransactionType::Eip7702) => {
+                return Err(TransactionError::InvalidField("to"))
+            }
+            [] => None,
+            to => Some(Address(
+                to.try_into()
+                    .map_err(|_| TransactionError::InvalidField("to"))?,
+            )),
+        };
+
+        let v = signature[0]
+            .uint()
+            .ok_or(TransactionError::InvalidField("v"))?;
+        let r = signature[1]
+            .word()
+            .ok_or(TransactionError::InvalidField("r"))?;
+        let s = signature[2]
+            .word()
+            .ok_or(TransactionError::InvalidField("s"))?;
+
+        let mut unsigned = Vec::with_capacity(payload.len());
+        let (chain_id, y_parity) = match kind.type_byte() {
+            None => {
+                let chain_id = (v >= 35).then(|| (v - 35) / 2);
+                let mut body = fields
+                    .iter()
+                    .flat_map(|item| item.raw)
+                    .copied()
+                    .collect::<Vec<_>>();
+                if let Some(chain_id) = chain_id {
+                    encode_uint(chain_id, &mut body);
+                    body.extend([0x80, 0x80]);
+                }
+                encode_header(0xc0, body.len(), &mut unsigned);
+                unsigned.extend(body);
+
+                // Legacy transactions encode the recovery ID with an offset
+                // of 27, or `chain_id * 2 + 35` with EIP-155.
+                let y_parity = match v {
+                    27 | 28 | 35.. => recover::y_parity(v).map_err(TransactionError::Signature)?,
+                    _ => return Err(TransactionError::InvalidField("v")),
+                };
+                (chain_id, y_parity)
+            }
+            Some(ty) => {
+                let chain_id = fields[0]
+                    .uint()
+                    .ok_or(TransactionError::InvalidField("chainId"))?;
+                let body_len = fields.iter().map(|item| item.raw.len()).sum();
+                unsigned.push(ty);
+                encode_header(0xc0, body_len, &mut unsigned);
+                unsigned.extend(fields.iter().flat_map(|item| item.raw));
+
+                if v > 1 {
+                    return Err(TransactionError::InvalidField("yParity"));
+                }
+                (Some(chain_id), v as u8)
+            }
+        };
+
+        let signing_hash = checksum::keccak256(&unsigned);
+        let from = if high_s && chain_id.is_none() {
+            // Transactions from before the Homestead hard fork can have high
+            // `s` values, and they can only be legacy transactions without
+            // replay protection.
+            recover_high_s(&signing_hash, r, s, y_parity)
+        } else {
+            recover::recover(&signing_hash, r, s, y_parity)
+        }
+        .map_err(TransactionError::Signature)?;
+
+        // The transaction hash is computed over the canonical encoding, which
+        // for EIP-4844 transactions in their network form, excludes the blobs
+        // and their commitments and proofs.
+        let hash = match kind.type_byte() {
+            None => checksum::keccak256(raw),
+            Some(ty) => {
+                let mut canonical = Vec::with_capacity(payload.len());
+                canonical.push(ty);
+                encode_header(
+                    0xc0,
+                    list.iter().map(|item| item.raw.len()).sum(),
+                    &mut canonical,
+                );
+                canonical.extend(list.iter().flat_map(|item| item.raw));
+                checksum::keccak256(&canonical)
+            }
+        };
+
+        Ok(Self {
+            kind,
+            chain_id,
+            from,
+            to,
+            hash,
+            signing_hash,
+        })
+    }
+}
+
+/// An RLP item.
+struct Item<'a> {
+    /// The raw encoding of the item, including its header.
+    raw: &'a [u8],
+    /// The payload of the item.
+    payload: &'a [u8],
+    /// Whether or not the item is a list.
+    is_list: bool,
+}
+
+impl<'a> Item<'a> {
+    /// Decodes a single RLP item from the start of some bytes, returning the
+    /// item and the remaining bytes.
+    fn decode(bytes: &'a [u8]) -> Result<(Self, &'a [u8]), TransactionError> {
+        let invalid = || TransactionError::InvalidRlp;
+        let (offset, len, is_list) = match *bytes.first().ok_or_else(invalid)? {
+            0x00..=0x7f => (0, 1, false),
+            b @ 0x80..=0xb7 => (1, (b - 0x80) as usize, false),
+            b @ 0xb8..=0xbf => (1 + (b - 0xb7) as usize, long_len(bytes, b - 0xb7)?, false),
+            b @ 0xc0..=0xf7 => (1, (b - 0xc0) as usize, true),
+            b @ 0xf8..=0xff => (1 + (b - 0xf7) as usize, long_len(bytes, b - 0xf7)?, true),
+        };
+
+        let end = offset.checked_add(len).ok_or_else(invalid)?;
+        let payload = bytes.get(offset..end).ok_or_else(invalid)?;
+        // Single bytes below `0x80` must be encoded as themselves.
+        if !is_list && offset == 1 && len == 1 && payload[0] < 0x80 {
+            return Err(invalid());
+        }
+
+        let item = Self {
+            raw: &bytes[..end],
+            payload,
+            is_list,
+        };
+        Ok((item, &bytes[end..]))
+    }
+
+    /// Decodes a single RLP item that spans all of the specified bytes.
+    fn decode_all(bytes: &'a [u8]) -> Result<Self, TransactionError> {
+        match Self::decode(bytes)? {
+            (item, []) => Ok(item),
+            _ => Err(TransactionError::InvalidRlp),
+        }
+    }
+
+    /// Returns the items of an RLP list.
+    fn list(&self) -> Result<Vec<Self>, TransactionError> {
+        if !self.is_list {
+            return Err(TransactionError::InvalidRlp);
+        }
+        let mut items = Vec::new();
+        let mut rest = self.payload;
+        while !rest.is_empty() {
+            let (item, next) = Self::decode(rest)?;
+            items.push(item);
+            rest = next;
+        }
+        Ok(items)
+    }
+
+    /// Returns the payload of an RLP string.
+    fn string(&self) -> Result<&'a [u8], TransactionError> {
+        if self.is_list {
+            return Err(TransactionError::InvalidRlp);
+        }
+        Ok(self.payload)
+    }
+
+    /// Returns the value of an RLP encoded 64-bit unsigned integer.
+    fn uint(&self) -> Option<u64> {
+        let bytes = self.string().ok()?;
+        if bytes.len() > 8 || bytes.first() == Some(&0) {
+            return None;
+        }
+        Some(bytes.iter().fold(0, |n, byte| (n << 8) + *byte as u64))
+    }
+
+    /// Returns the value of an RLP encoded 256-bit unsigned integer as a
+    /// big-endian 32-byte word.
+    fn word(&self) -> Option<[u8; 32]> {
+        let bytes = self.string().ok()?;
+        if bytes.len() > 32 || bytes.first() == Some(&0) {
+            return None;
+        }
+        let mut word = [0; 32];
+        word[32 - bytes.len()..].copy_from_slice(bytes);
+        Some(word)
+    }
+}
+
+/// Reads the big-endian length of a long RLP string or list.
+fn long_len(bytes: &[u8], len_of_len: u8) -> Result<usize, TransactionError> {
+    let len_bytes = bytes
+        .get(1..1 + len_of_len as usize)
+        .ok_or(TransactionError::InvalidRlp)?;
+    if len_bytes[0] == 0 || len_bytes.len() > 8 {
+        return Err(TransactionError::InvalidRlp);
+    }
+    let len = len_bytes
+        .iter()
+        .fold(0_u64, |n, byte| (n << 8) + *byte as u64);
+    if len < 56 {
+        return Err(TransactionError::InvalidRlp);
+    }
+    usize::try_from(len).map_err(|_| TransactionError::InvalidRlp)
+}
+
+/// Encodes an RLP string or list header.
+fn encode_header(offset: u8, len: usize, buffer: &mut Vec<u8>) {
+    if len < 56 {
+        buffer.push(offset + len as u8);
+    } else {
+        let len_bytes = (len as u64).to_be_bytes();
+        let len_bytes = &len_bytes[(len as u64).leading_zeros() as usize / 8..];
+        buffer.push(offset + 55 + len_bytes.len() as u8);
+        buffer.extend(len_bytes);
+    }
+}
+
+/// Encodes a 64-bit unsigned integer as an RLP string.
+fn encode_uint(value: u64, buffer: &mut Vec<u8>) {
+    let bytes = value.to_be_bytes();
+    let bytes = &bytes[value.leading_zeros() as usize / 8..];
+    match bytes {
+        [byte] if *byte < 0x80 => buffer.push(*byte),
+        _ => {
+            encode_header(0x80, bytes.len(), buffer);
+            buffer.extend(bytes);
+        }
+    }
+}
+
+/// Recovers the signer address from an ECDSA signature's `r`, `s` and
+/// `y_parity` components, accepting signatures with high `s` values.
+fn recover_high_s(
+    prehash: &[u8; 32],
+    r: [u8; 32],
+    s: [u8; 32],
+    y_parity: u8,
+) -> Result<Address, RecoverError> {
+    let signature = Signature::from_scalars(r, s).map_err(|_| RecoverError::InvalidSignature)?;
+    match signature.normalize_s() {
+        // Negating `s` negates the public key recovered from the signature,
+        // so the normalized signature recovers the same key with the opposite
+        // `y` parity.
+        Some(normalized) => recover::recover_signature(prehash, &normalized, y_parity ^ 1),
+        None => recover::recover_signature(prehash, &signature, y_parity),
+    }
+}
+
+/// Represents an error decoding a signed transaction.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum TransactionError {
+    /// The transaction is not valid RLP.
+    InvalidRlp,
+    /// The transaction type is not supported.
+    UnsupportedType(u8),
+    /// The transaction does not have the expected number of fields.
+    InvalidFieldCount,
+    /// A transaction field has an invalid value.
+    InvalidField(&'static str),
+    /// The transaction signature is invalid.
+    Signature(RecoverError),
+}
+
+impl Display for TransactionError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Self::InvalidRlp => write!(f, "invalid transaction RLP encoding"),
+            Self::UnsupportedType(ty) => write!(f, "unsupported transaction type {ty:#04x}"),
+            Self::InvalidFieldCount => write!(f, "invalid number of transaction fields"),
+            Self::InvalidField(name) => write!(f, "invalid transaction field `{name}`"),
+            Self::Signature(err) => write!(f, "invalid transaction signature: {err}"),
+        }
+    }
+}
+
+impl std::error::Error for TransactionError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Signature(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::bytes;
+    use k256::ecdsa::SigningKey;
+
+    fn string(value: &[u8]) -> Vec<u8> {
+        let mut buffer = Vec::new();
+        match value {
+            [byte] if *byte < 0x80 => buffer.push(*byte),
+            _ => {
+                encode_header(0x80, value.len(), &mut buffer);
+                buffer.extend(value);
+            }
+        }
+        buffer
+    }
+
+    fn uint(value: u64) -> Vec<u8> {
+        let mut buffer = Vec::new();
+        encode_uint(value, &mut buffer);
+        buffer
+    }
+
+    fn list(items: &[Vec<u8>]) -> Vec<u8> {
+        let mut buffer = Vec::new();
+        encode_header(0xc0, items.iter().map(Vec::len).sum(), &mut buffer);
+        buffer.extend(items.concat());
+        buffer
+    }
+
+    /// Signs a typed transaction from its unsigned fields.
+    fn sign_typed(key: &SigningKey, ty: u8, fields: &[Vec<u8>]) -> (Vec<u8>, [u8; 32]) {
+        let signing_hash = checksum::keccak256(&[&[ty][..], &list(fields)].concat());
+        let (signature, recovery_id) = key.sign_prehash_recoverable(&signing_hash).unwrap();
+        let (r, s) = signature.split_bytes();
+
+        let mut signed = fields.to_vec();
+        signed.push(uint(recovery_id.to_byte() as _));
+        signed.push(string(r.as_slice().strip_prefix(&[0]).unwrap_or(&r)));
+        signed.push(string(s.as_slice().strip_prefix(&[0]).unwrap_or(&s)));
+        ([&[ty][..], &list(&signed)].concat(), signing_hash)
+    }
+
+    fn signer(key: &SigningKey) -> Address {
+        Address::from_public_key(key.verifying_key().to_encoded_point(false).as_bytes()).unwrap()
+    }
+
+    #[test]
+    fn eip155_example() {
+        let raw = bytes(
+            "f86c098504a817c800825208943535353535353535353535353535353535353535880de0b6b3a7640000\
+             8025a028ef61340bd939bc2195fe537567866003e1a15d3c71ff63e1590620aa636276a067cbe9d8997f\
+             761aecb703304b3800ccf555c9f3dc64214b297fb1966a3b6d83",
+        );
+        let transaction = Transaction::decode(&raw).unwrap();
+
+        assert_eq!(
+            transaction.signing_hash[..],
+            bytes("daf5a779ae972f972197303d7b574746c7ef83eadac0f2791ad23db92e4c8e53"),
+        );
+        assert_eq!(
+            transaction.from,
+            Address::from_str_checksum("0x9d8A62f656a8d1615C1294fd71e9CFb3E4855A4F").unwrap(),
+        );
+        assert_eq!(transaction.to, Some(Address([0x35; 20])));
+        assert_eq!(transaction.hash, checksum::keccak256(&raw));
+    }
+
+    #[test]
+    fn network_transactions() {
+        for (raw, kind, chain_id, hash, from, to) in [
+            // The first transaction on mainnet, from before EIP-155.
+            (
+                "f86780862d79883d2000825208945df9b87991262f6ba471f09758cde1c0fc1de734827a69801ca088ff\
+                 6cf0fefd94db46111149ae4bfc179e9b94721fffd821d38d16464b3f71d0a045e0aff800961cfce805da\
+                 ef7016b9b675c137a6a41a548f7b60a3484c06a33a",
+                TransactionType::Legacy,
+                None,
+                "5c504ed432cb51138bcf09aa5e8a410dd4a1e204ef84bfed1be16dfba1b22060",
+                "0xA1E4380A3B1f749673E270229993eE55F35663b4",
+                "0x5DF9B87991262F6BA471F09758CDE1c0FC1De734",
+            ),
+            (
+                "f9015482078b8505d21dba0083022ef1947a250d5630b4cf539739df2c5dacb4c659f2488d880c46549a\
+                 521b13d8b8e47ff36ab50000000000000000000000000000000000000000000066ab5a608bd00a23f2fe\
+                 000000000000000000000000000000000000000000000000000000000000008000000000000000000000\
+                 000048c04ed5691981c42154c6167398f95e8f38a7ff0000000000000000000000000000000000000000\
+                 0000000000000000632ceac7000000000000000000000000000000000000000000000000000000000000\
+                 0002000000000000000000000000c02aaa39b223fe8d0a0e5c4f27ead9083c756cc20000000000000000\
+                 000000006c6ee5e31d828de241282b9606c8e98ea48526e225a0c9077369501641a92ef7399ff81c2163\
+                 9ed4fd8fc69cb793cfa1dbfab342e10aa0615facb2f1bcf3274a354cfe384a38d0cc008a11c2dd23a691\
+                 11bc6930ba27a8",
+                TransactionType::Legacy,
+                Some(1),
+                "280cde7cdefe4b188750e76c888f13bd05ce9a4d7767730feefe8a0e50ca6fc4",
+                "0xa12e1462d0ceD572f396F58B6E2D03894cD7C8a4",
+                "0x7a250d5630B4cF539739dF2C5dAcb4c659F2488D",
+            ),
+            (
+                "02f872018307910d808507204d2cb1827d0094388c818ca8b9251b393131c08a736a67ccb19297880320\
+                 d04823e2701c80c001a0cf024f4815304df2867a1a74e9d2707b6abda0337d2d54a4438d453f4160f190\
+                 a07ac0e6b3bc9395b5b9c8b9e6d77204a236577a5b18467b9175c01de4faa208d9",
+                TransactionType::Eip1559,
+                Some(1),
+                "86718885c4b4218c6af87d3d0b0d83e3cc465df2a05c048aa4db9f1a6f9de91f",
+                "0x95222290DD7278Aa3Ddd389Cc1E1d165CC4BAfe5",
+                "0x388C818CA8B9251b393131C08a736A67ccB19297",
+            ),
+            (
+                "02f86f0102843b9aca0085029e7822d68298f094d9e1459a7a482635700cbc20bbaf52d495ab9c968084\
+                 1b55ba3ac080a0c199674fcb29f353693dd779c017823b954b3c69dffa3cd6b2a6ff7888798039a028ca\
+                 912de909e7e6cdef9cdcaf24c54dd8c1032946dfa1d85c206b32a9064fe8",
+                TransactionType::Eip1559,
+                Some(1),
+                "ce4dc6d7a7549a98ee3b071b67e970879ff51b5b95d1c340bacd80fa1e1aab31",
+                "0x001e2b7dE757bA469a57bF6b23d982458a07eFcE",
+                "0xD9e1459A7A482635700cBc20BBAF52D495Ab9C96",
+            ),
+            // There are no blob transactions on mainnet that are small enough to
+            // include here, so use one from Sepolia instead.
+            (
+                "03f9011d83aa36a7820fa28477359400852e90edd0008252089411e9ca82a3a762b4b5bd264d4173a242\
+                 e7a770648080c08504a817c800f8a5a0012ec3d6f66766bedb002a190126b3549fce0047de0d4c25cffc\
+                 e0dc1c57921aa00152d8e24762ff22b1cfd9f8c0683786a7ca63ba49973818b3d1e9512cd2cec4a0013b\
+                 98c6c83e066d5b14af2b85199e3d4fc7d1e778dd53130d180f5077e2d1c7a001148b495d6e859114e670\
+                 ca54fb6e2657f0cbae5b08063605093a4b3dc9f8f1a0011ac212f13c5dff2b2c6b600a79635103d6f580\
+                 a4221079951181b25c7e654901a0c8de4cced43169f9aa3d36506363b2d2c44f6c49fc1fd91ea114c86f\
+                 3757077ea01e11fdd0d1934eda0492606ee0bb80a7bf8f35cc5f86ec60fe5031ba48bfd544",
+                TransactionType::Eip4844,
+                Some(11155111),
+                "9a22ccb0029bc8b0ddd073be1a1d923b7ae2b2ea52100bae0db4424f9107e9c0",
+                "0xA83C816D4f9b2783761a22BA6FADB0eB0606D7B2",
+                "0x11E9CA82A3a762b4B5bd264d4173a242e7a77064",
+            ),
+        ] {
+            let transaction = Transaction::decode(&bytes(raw)).unwrap();
+
+            assert_eq!(transaction.kind, kind);
+            assert_eq!(transaction.chain_id, chain_id);
+            assert_eq!(transaction.hash[..], bytes(hash));
+            assert_eq!(transaction.from, Address::from_str_checksum(from).unwrap());
+            assert_eq!(transaction.to, Some(Address::from_str_checksum(to).unwrap()));
+        }
+    }
+
+    #[test]
+    fn high_s_signatures() {
+        // The first transaction on mainnet with its signature `s` value
+        // negated, which is only allowed for legacy transactions from before
+        // the Homestead hard fork.
+        let raw = bytes(
+            "f86780862d79883d2000825208945df9b87991262f6ba471f09758cde1c0fc1de734827a69801ba088ff\
+             6cf0fefd94db46111149ae4bfc179e9b94721fffd821d38d16464b3f71d0a0ba1f5007ff69e30317fa25\
+             108fe9464844eda5400b2e4bac4471bb44842f9e07",
+        );
+        let transaction = Transaction::decode_lenient(&raw).unwrap();
+        assert_eq!(
+            transaction.from,
+            Address::from_str_checksum("0xA1E4380A3B1f749673E270229993eE55F35663b4").unwrap(),
+        );
+        assert_eq!(transaction.hash, checksum::keccak256(&raw));
+        assert_eq!(
+            Transaction::decode(&raw),
+            Err(TransactionError::Signature(RecoverError::HighS)),
+        );
+
+        // High `s` values are always rejected for transactions with replay
+        // protection.
+        let raw = bytes(
+            "f86c098504a817c800825208943535353535353535353535353535353535353535880de0b6b3a7640000\
+             8026a028ef61340bd939bc2195fe537567866003e1a15d3c71ff63e1590620aa636276a0983416276680\
+             89e51348fccfb4c7ff31c55912f2d2e47ef09652acf665fad3be",
+        );
+        for decode in [Transaction::decode, Transaction::decode_lenient] {
+            assert_eq!(
+                decode(&raw),
+                Err(TransactionError::Signature(RecoverError::HighS)),
+            );
+        }
+    }
+
+    #[test]
+    fn legacy_without_replay_protection() {
+        let key = SigningKey::from_slice(&[0x01; 32]).unwrap();
+        let fields = [
+            uint(0),
+            uint(1_000_000_000),
+            uint(53_000),
+            string(&[]),
+            uint(0),
+            string(&[0x60, 0x00, 0x60, 0x00, 0xf3]),
+        ];
+        let signing_hash = checksum::keccak256(&list(&fields));
+        let (signature, recovery_id) = key.sign_prehash_recoverable(&signing_hash).unwrap();
+        let (r, s) = signature.split_bytes();
+
+        let mut signed = fields.to_vec();
+        signed.push(uint(recovery_id.to_byte() as u64 + 27));
+        signed.push(string(r.as_slice().strip_prefix(&[0]).unwrap_or(&r)));
+        signed.push(string(s.as_slice().strip_prefix(&[0]).unwrap_or(&s)));
+        let transaction = Transaction::decode(&list(&signed)).unwrap();
+
+        assert_eq!(transaction.kind, TransactionType::Legacy);
+        assert_eq!(transaction.chain_id, None);
+        assert_eq!(transaction.from, signer(&key));
+        assert_eq!(transaction.to, None);
+        assert_eq!(transaction.signing_hash, signing_hash);
+    }
+
+    #[test]
+    fn typed_transactions() {
+        let key = SigningKey::from_slice(&[0x01; 32]).unwrap();
+        let to = Address([0x42; 20]);
+        let access_list = list(&[list(&[string(&to[..]), list(&[string(&[0x11; 32])])])]);
+        let data = string(&[0xab; 100]);
+
+        for (ty, kind, fields) in [
+            (
+                0x01,
+                TransactionType::Eip2930,
+                vec![
+                    uint(1),
+                    uint(7),
+                    uint(1_000_000_000),
+                    uint(21_000),
+                    string(&to[..]),
+                    uint(1),
+                    data.clone(),
+                    access_list.clone(),
+                ],
+            ),
+            (
+                0x02,
+                TransactionType::Eip1559,
+                vec![
+                    uint(10),
+                    uint(7),
+                    uint(1_000_000_000),
+                    uint(2_000_000_000),
+                    uint(21_000),
+                    string(&to[..]),
+                    uint(1),
+                    data.clone(),
+                    access_list.clone(),
+                ],
+            ),
+            (
+                0x03,
+                TransactionType::Eip4844,
+                vec![
+                    uint(11155111),
+                    uint(7),
+                    uint(1_000_000_000),
+                    uint(2_000_000_000),
+                    uint(21_000),
+                    string(&to[..]),
+                    uint(0),
+                    data.clone(),
+                    access_list.clone(),
+                    uint(1),
+                    list(&[string(&[0x01; 32])]),
+                ],
+            ),
+            (
+                0x04,
+                TransactionType::Eip7702,
+                vec![
+                    uint(1),
+                    uint(7),
+                    uint(1_000_000_000),
+                    uint(2_000_000_000),
+                    uint(21_000),
+                    string(&to[..]),
+                    uint(0),
+                    data.clone(),
+                    access_list.clone(),
+                    list(&[list(&[
+                        uint(1),
+                        string(&[0x33; 20]),
+                        uint(0),
+                        uint(1),
+                        string(&[0x44; 32]),
+                        string(&[0x55; 32]),
+                    ])]),
+                ],
+            ),
+        ] {
+            let (raw, signing_hash) = sign_typed(&key, ty, &fields);
+            let transaction = Transaction::decode(&raw).unwrap();
+
+            assert_eq!(transaction.kind, kind);
+            assert_eq!(transaction.kind.type_byte(), Some(ty));
+            assert_eq!(transaction.from, signer(&key));
+            assert_eq!(transaction.to, Some(to));
+            assert_eq!(transaction.signing_hash, signing_hash);
+            assert_eq!(transaction.hash, checksum::keccak256(&raw));
+        }
+    }
+
+    #[test]
+    fn eip4844_network_form() {
+        let key = SigningKey::from_slice(&[0x01; 32]).unwrap();
+        let fields = [
+            uint(1),
+            uint(0),
+            uint(1),
+            uint(1),
+            uint(21_000),
+            string(&[0x42; 20]),
+            uint(0),
+            string(&[]),
+            list(&[]),
+            uint(1),
+            list(&[string(&[0x01; 32])]),
+        ];
+        let (raw, _) = sign_typed(&key, 0x03, &fields);
+
+        let network = [
+            &[0x03][..],
+            &list(&[
+                raw[1..].to_vec(),
+                list(&[string(&[0; 256])]),
+                list(&[string(&[0; 48])]),
+                list(&[string(&[0; 48])]),
+            ]),
+        ]
+        .concat();
+        let transaction = Transaction::decode(&network).unwrap();
+
+        assert_eq!(transaction.from, signer(&key));
+        assert_eq!(transaction.hash, checksum::keccak256(&raw));
+    }
+
+    #[test]
+    fn invalid_transactions() {
+        assert_eq!(Transaction::decode(&[]), Err(TransactionError::InvalidRlp));
+        assert_eq!(
+            Transaction::decode(&[0x05, 0xc0]),
+            Err(TransactionError::UnsupportedType(0x05)),
+        );
+        assert_eq!(
+            Transaction::decode(&[0x02, 0xc1, 0x01]),
+            Err(TransactionError::InvalidFieldCount),
+        );
+        // Non-canonical single byte string encoding.
+        assert_eq!(
+            Transaction::decode(&[0x02, 0xc2, 0x81, 0x01]),
+            Err(TransactionError::InvalidRlp),
+        );
+        // Trailing bytes.
+        assert_eq!(
+            Transaction::decode(&[0x02, 0xc0, 0x00]),
+            Err(TransactionError::InvalidRlp),
+        );
+
+        let key = SigningKey::from_slice(&[0x01; 32]).unwrap();
+        let mut fields = vec![
+            uint(1),
+            uint(0),
+            uint(1),
+            uint(1),
+            uint(21_000),
+            string(&[]),
+            uint(0),
+            string(&[]),
+            list(&[]),
+            list(&[]),
+        ];
+        let (raw, _) = sign_typed(&key, 0x04, &fields);
+        assert_eq!(
+            Transaction::decode(&raw),
+            Err(TransactionError::InvalidField("to")),
+        );
+
+        fields[5] = string(&[0x42; 20]);
+        fields.extend([uint(2), string(&[0x01; 32]), string(&[0x01; 32])]);
+        assert_eq!(
+            Transaction::decode(&[&[0x04][..], &list(&fields)].concat()),
+            Err(TransactionError::InvalidField("yParity")),
+        );
+    }
+}